@@ -0,0 +1,322 @@
+//! Strongly connected components
+//!
+//! <https://en.wikipedia.org/wiki/Strongly_connected_component>
+//!
+//! Two nodes are in the same strongly connected component iff each can be
+//! reached from the other. Contracting every component to a single node
+//! gives a directed acyclic graph.
+//!
+//! Both algorithms return the components in reverse topological order: no
+//! component has an edge to a component that comes after it. Processing the
+//! components in order therefore visits callees before their callers.
+
+use crate::{
+    graph::{
+        adjlist,
+        traits::{Children, NodeCount, Parents},
+    },
+    traversal::postorder::PostOrder,
+    Error,
+};
+
+/// Returns the strongly connected components of a graph in reverse
+/// topological order, using Tarjan's algorithm.
+///
+/// The nodes of each component are in the order they were discovered.
+/// The search is iterative, so deep graphs don't overflow the stack.
+///
+/// Time complexity: **O(|V| + |E|)**
+#[must_use]
+pub fn strongly_connected<G>(graph: G) -> Vec<Vec<usize>>
+where
+    G: Children + NodeCount,
+{
+    let node_count = graph.node_count();
+    // The order each node was discovered in
+    let mut index: Vec<Option<usize>> = vec![None; node_count];
+    // The smallest index reachable from each node through its subtree
+    let mut lowlink = vec![0; node_count];
+    let mut on_stack = vec![false; node_count];
+    // The nodes that haven't been assigned a component yet
+    let mut stack = Vec::new();
+    // The nodes being searched and their unexplored children
+    let mut search: Vec<(usize, G::Iter)> = Vec::new();
+    let mut components = Vec::new();
+    let mut count = 0;
+
+    for root in 0..node_count {
+        if index[root].is_some() || !graph.contains_node(root) {
+            continue;
+        }
+        index[root] = Some(count);
+        lowlink[root] = count;
+        count += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        search.push((root, graph.children(root)));
+
+        while let Some((node, children)) = search.last_mut() {
+            let node = *node;
+            if let Some(child) = children.next() {
+                match index[child] {
+                    None => {
+                        index[child] = Some(count);
+                        lowlink[child] = count;
+                        count += 1;
+                        stack.push(child);
+                        on_stack[child] = true;
+                        search.push((child, graph.children(child)));
+                    }
+                    Some(child_index) if on_stack[child] => {
+                        lowlink[node] = lowlink[node].min(child_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            search.pop();
+            if let Some(&(parent, _)) = search.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[node]);
+            }
+            if Some(lowlink[node]) == index[node] {
+                // `node` is the root of a component made of the nodes above it
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                component.reverse();
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+/// Returns the strongly connected components of a graph in reverse
+/// topological order, using Kosaraju's algorithm.
+///
+/// Searches the graph backwards through the parents of each node instead of
+/// building the transpose. The nodes of each component are in the order
+/// they were discovered.
+///
+/// Time complexity: **O(|V| + |E|)**
+#[must_use]
+pub fn kosaraju<G>(graph: G) -> Vec<Vec<usize>>
+where
+    G: Children + Parents + NodeCount,
+{
+    let node_count = graph.node_count();
+    let roots = (0..node_count).filter(|&node| graph.contains_node(node));
+    let mut order = PostOrder::from_roots(graph, roots).collect::<Vec<_>>();
+    let mut assigned = vec![false; node_count];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+
+    // The node that finished last is in a component without parents outside
+    // of it, so the components are found in topological order.
+    while let Some(root) = order.pop() {
+        if assigned[root] {
+            continue;
+        }
+        assigned[root] = true;
+        stack.push(root);
+        let mut component = Vec::new();
+        while let Some(node) = stack.pop() {
+            component.push(node);
+            for parent in graph.parents(node) {
+                if !assigned[parent] {
+                    assigned[parent] = true;
+                    stack.push(parent);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components.reverse();
+    components
+}
+
+/// Contracts each component of a graph to a single node, e.g. the strongly
+/// connected components from [`strongly_connected`].
+///
+/// Returns the condensed graph, where node `i` is `components[i]` and there
+/// is at most one edge between two components, along with the component of
+/// each node of the original graph, or `None` for vacant slots like the
+/// removed nodes of a [`StableGraph`](crate::graph::stable::StableGraph).
+/// Edges within a component are dropped, so the condensation of the strongly
+/// connected components is acyclic.
+///
+/// Time complexity: **O(|V| + |E|)**
+///
+/// # Panics
+///
+/// Panics if the components contain a node that isn't in the graph, or if a
+/// node isn't in any of the components. See [`try_condensation`].
+#[must_use]
+pub fn condensation<G>(graph: G, components: &[Vec<usize>]) -> (adjlist::Graph, Vec<Option<usize>>)
+where
+    G: Children + NodeCount,
+{
+    try_condensation(graph, components).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// Contracts each component of a graph to a single node, like [`condensation`].
+///
+/// # Errors
+///
+/// Returns [`Error::NodeOutOfRange`] if the components contain a node that
+/// isn't in the graph and [`Error::UncoveredNode`] if a node isn't in any of
+/// the components.
+pub fn try_condensation<G>(
+    graph: G,
+    components: &[Vec<usize>],
+) -> Result<(adjlist::Graph, Vec<Option<usize>>), Error>
+where
+    G: Children + NodeCount,
+{
+    let node_count = graph.node_count();
+    let mut component_of = vec![None; node_count];
+    for (component, nodes) in components.iter().enumerate() {
+        for &node in nodes {
+            if !graph.contains_node(node) {
+                return Err(Error::NodeOutOfRange { node, node_count });
+            }
+            component_of[node] = Some(component);
+        }
+    }
+    if let Some(node) =
+        (0..node_count).find(|&node| component_of[node].is_none() && graph.contains_node(node))
+    {
+        return Err(Error::UncoveredNode(node));
+    }
+
+    let mut condensed = adjlist::Graph::with_capacity(components.len(), 0);
+    for _ in components {
+        condensed.add_node();
+    }
+    // The last component that an edge to each component was added from
+    let mut linked_from = vec![None; components.len()];
+    for (component, nodes) in components.iter().enumerate() {
+        for &node in nodes {
+            for child in graph.children(node) {
+                let Some(target) = component_of[child] else {
+                    continue;
+                };
+                if target != component && linked_from[target] != Some(component) {
+                    linked_from[target] = Some(component);
+                    condensed.push(component, target);
+                }
+            }
+        }
+    }
+    Ok((condensed, component_of))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::{condensation, kosaraju, strongly_connected, try_condensation},
+        graph::{adjlist, matrix::Graph, traits::Directed},
+        traversal::topological::Topological,
+        Error,
+    };
+
+    /// Sorts the nodes of each component so the results can be compared.
+    fn sorted(mut components: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        for component in &mut components {
+            component.sort_unstable();
+        }
+        components
+    }
+
+    #[test]
+    fn test_strongly_connected() {
+        // https://en.wikipedia.org/wiki/Strongly_connected_component
+        let graph = Graph::<_, Directed>::from([
+            (0, 1),
+            (1, 2),
+            (1, 4),
+            (1, 5),
+            (2, 3),
+            (2, 6),
+            (3, 2),
+            (3, 7),
+            (4, 0),
+            (4, 5),
+            (5, 6),
+            (6, 5),
+            (7, 3),
+            (7, 6),
+        ]);
+        let expected = [vec![5, 6], vec![2, 3, 7], vec![0, 1, 4]];
+        assert_eq!(sorted(strongly_connected(&graph)), expected);
+        assert_eq!(sorted(kosaraju(&graph)), expected);
+    }
+
+    #[test]
+    fn test_acyclic() {
+        let graph = Graph::<_, Directed>::from([(0, 1), (1, 2), (0, 2), (3, 3)]);
+        let expected = [vec![2], vec![1], vec![0], vec![3]];
+        assert_eq!(strongly_connected(&graph), expected);
+        assert_eq!(kosaraju(&graph), expected);
+    }
+
+    #[test]
+    fn test_deep() {
+        // a single cycle through every node
+        let node_count = 100_000;
+        let graph = (0..node_count)
+            .map(|node| (node, (node + 1) % node_count))
+            .collect::<adjlist::Graph>();
+        assert_eq!(strongly_connected(&graph).len(), 1);
+        assert_eq!(kosaraju(&graph).len(), 1);
+    }
+
+    #[test]
+    fn test_condensation() {
+        let graph =
+            Graph::<_, Directed>::from([(0, 1), (1, 0), (1, 2), (0, 2), (2, 3), (3, 2), (3, 4)]);
+        let components = strongly_connected(&graph);
+        let (condensed, component_of) = condensation(&graph, &components);
+        assert_eq!(component_of, [Some(2), Some(2), Some(1), Some(1), Some(0)]);
+        assert_eq!(condensed.len(), 3);
+        assert_eq!(condensed.edge_count(), 2);
+
+        // the components are in reverse topological order
+        let order = Topological::new(&condensed).collect::<Vec<_>>();
+        assert_eq!(order, [2, 1, 0]);
+
+        // components without edges are kept
+        let graph = Graph::<_, Directed>::from([(1, 0), (0, 1), (2, 2)]);
+        let (condensed, component_of) = condensation(&graph, &strongly_connected(&graph));
+        assert_eq!(component_of, [Some(0), Some(0), Some(1)]);
+        assert_eq!(condensed.len(), 2);
+        assert!(condensed.is_empty());
+    }
+
+    #[test]
+    fn test_try_condensation() {
+        let graph = Graph::<_, Directed>::from([(0, 1), (1, 2)]);
+        assert_eq!(
+            try_condensation(&graph, &[vec![0, 1], vec![3]]).map(|_| ()),
+            Err(Error::NodeOutOfRange {
+                node: 3,
+                node_count: 3
+            })
+        );
+        assert_eq!(
+            try_condensation(&graph, &[vec![0], vec![2]]).map(|_| ()),
+            Err(Error::UncoveredNode(1))
+        );
+        let condensed = try_condensation(&graph, &[vec![0, 1], vec![2]]);
+        assert_eq!(
+            condensed.map(|(_, of)| of),
+            Ok(vec![Some(0), Some(0), Some(1)])
+        );
+    }
+}
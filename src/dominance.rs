@@ -16,32 +16,37 @@
 use crate::{
     graph::traits::{Children, NodeCount, Parents},
     traversal::postorder::PostOrder,
+    Error,
 };
 
 /// Finds the nearest common dominator of two nodes.
 /// Walks up the dominator tree from two different nodes until a common parent is reached.
-#[allow(clippy::expect_used)]
+/// Returns `None` if a node without a dominator is reached first.
 fn nearest_common_dominator(
     dominators: &[Option<usize>],
     postorder: &[usize],
     mut finger1: usize,
     mut finger2: usize,
-) -> usize {
+) -> Option<usize> {
     while finger1 != finger2 {
         while postorder[finger1] < postorder[finger2] {
-            finger1 = dominators[finger1].expect("Shouldn't happen");
+            finger1 = dominators[finger1]?;
         }
         while postorder[finger2] < postorder[finger1] {
-            finger2 = dominators[finger2].expect("Shouldn't happen");
+            finger2 = dominators[finger2]?;
         }
     }
-    finger1
+    Some(finger1)
 }
 
 /// Returns the immediate dominators of all nodes of a `Graph`.
 ///
 /// Except for `start`, the immediate dominators are the parents of their
 /// corresponding nodes in the dominator tree.
+///
+/// # Panics
+///
+/// Panics if `start` isn't a node in the graph.
 #[must_use]
 pub fn immediate_dominators<G>(graph: G, start: usize) -> Vec<Option<usize>>
 where
@@ -66,9 +71,11 @@ where
             let new_idom = graph
                 .parents(node)
                 .filter(|&predecessor| dominators[predecessor].is_some())
+                .map(Some)
                 .reduce(|finger1, finger2| {
-                    nearest_common_dominator(&dominators, &postorder_idx, finger1, finger2)
-                });
+                    nearest_common_dominator(&dominators, &postorder_idx, finger1?, finger2?)
+                })
+                .flatten();
             // The root is initialized to dominate itself, and is the first node in
             // every path so there must exist a predecessor to this node that also
             // has a dominator.
@@ -82,12 +89,28 @@ where
     dominators
 }
 
+/// Returns the immediate dominators of all nodes, like [`immediate_dominators`].
+///
+/// # Errors
+///
+/// Returns [`Error::NodeOutOfRange`] if `start` isn't a node in the graph.
+pub fn try_immediate_dominators<G>(graph: G, start: usize) -> Result<Vec<Option<usize>>, Error>
+where
+    G: Children + Parents + NodeCount,
+{
+    check_start(graph, start)?;
+    Ok(immediate_dominators(graph, start))
+}
+
 /// Returns the dominance frontiers of all nodes of a directed graph.
 ///
 /// The dominance frontier of a node `b` is the set of all nodes `y`
 /// such that `b` dominates a predecessor of `y` but does not strictly
-/// dominate `y`.
-#[allow(clippy::expect_used)]
+/// dominate `y`. Nodes that can't be reached from `start` have no frontier.
+///
+/// # Panics
+///
+/// Panics if `start` isn't a node in the graph.
 pub fn frontiers<G>(graph: G, start: usize) -> Vec<Vec<usize>>
 where
     G: Children + Parents + NodeCount,
@@ -104,8 +127,10 @@ where
             for &predecessor in &predecessors {
                 let mut finger = predecessor;
                 while Some(finger) != idoms[node] {
+                    // unreachable predecessors have no dominators and are skipped
+                    let Some(idom) = idoms[finger] else { break };
                     frontiers[finger].push(node);
-                    finger = idoms[finger].expect("Shouldn't happen");
+                    finger = idom;
                 }
             }
         }
@@ -113,11 +138,38 @@ where
     frontiers
 }
 
+/// Returns the dominance frontiers of all nodes, like [`frontiers`].
+///
+/// # Errors
+///
+/// Returns [`Error::NodeOutOfRange`] if `start` isn't a node in the graph.
+pub fn try_frontiers<G>(graph: G, start: usize) -> Result<Vec<Vec<usize>>, Error>
+where
+    G: Children + Parents + NodeCount,
+{
+    check_start(graph, start)?;
+    Ok(frontiers(graph, start))
+}
+
+/// Returns an error if `start` isn't a node in the graph.
+fn check_start<G: NodeCount>(graph: G, start: usize) -> Result<(), Error> {
+    let node_count = graph.node_count();
+    if graph.contains_node(start) {
+        Ok(())
+    } else {
+        Err(Error::NodeOutOfRange {
+            node: start,
+            node_count,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        dominance::{frontiers, immediate_dominators},
+        dominance::{frontiers, immediate_dominators, try_frontiers, try_immediate_dominators},
         graph::{matrix::Graph, traits::Directed},
+        Error,
     };
 
     #[test]
@@ -195,4 +247,20 @@ mod tests {
             vec![vec![], vec![5], vec![4], vec![4], vec![5], vec![],]
         );
     }
+
+    #[test]
+    fn test_unreachable() {
+        // 3 can't be reached from 0 but is a predecessor of 2
+        let graph = Graph::<_, Directed>::from([(0, 1), (0, 2), (1, 2), (3, 2)]);
+        let frontier = frontiers(&graph, 0);
+        assert_eq!(frontier, vec![vec![], vec![2], vec![], vec![]]);
+        assert_eq!(
+            try_frontiers(&graph, 4),
+            Err(Error::NodeOutOfRange {
+                node: 4,
+                node_count: 4
+            })
+        );
+        assert!(try_immediate_dominators(&graph, 0).is_ok());
+    }
 }
@@ -0,0 +1,58 @@
+//! The error type returned by fallible graph operations.
+use core::fmt;
+
+/// Errors returned by the `try_` variants of graph operations and algorithms.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// A node index is not smaller than the number of nodes in the graph.
+    NodeOutOfRange {
+        /// The offending node.
+        node: usize,
+        /// The number of nodes in the graph.
+        node_count: usize,
+    },
+    /// The number of weights doesn't match the number of edges.
+    WeightLengthMismatch {
+        /// The number of edges in the graph.
+        expected: usize,
+        /// The number of weights given.
+        found: usize,
+    },
+    /// The graph contains a cycle but the operation requires it to be acyclic.
+    /// Contains the nodes of one of the cycles in edge order.
+    CycleDetected(Vec<usize>),
+    /// A cycle with a negative total cost was found, containing these nodes
+    /// in edge order.
+    NegativeCycle(Vec<usize>),
+    /// The node can't be reached from the start node.
+    UnreachableNode(usize),
+    /// The node isn't in any of the components given for the graph.
+    UncoveredNode(usize),
+    /// The graph can't grow any further, either because an index doesn't fit
+    /// in the index type or because the storage would overflow.
+    CapacityOverflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NodeOutOfRange { node, node_count } => {
+                write!(
+                    f,
+                    "node {node} out of range for graph with {node_count} nodes"
+                )
+            }
+            Self::WeightLengthMismatch { expected, found } => {
+                write!(f, "expected {expected} edge weights, found {found}")
+            }
+            Self::CycleDetected(nodes) => write!(f, "cycle through nodes {nodes:?}"),
+            Self::NegativeCycle(nodes) => write!(f, "negative cycle through nodes {nodes:?}"),
+            Self::UnreachableNode(node) => write!(f, "node {node} is unreachable"),
+            Self::UncoveredNode(node) => write!(f, "node {node} isn't in any component"),
+            Self::CapacityOverflow => f.write_str("graph capacity overflow"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
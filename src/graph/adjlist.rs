@@ -0,0 +1,594 @@
+//! A graph data structure using an adjacency list representation.
+
+use super::{
+    index::IndexType,
+    traits::{Children, Incoming, NodeCount, Outgoing, Parents},
+};
+use crate::Error;
+
+/// Index of the outgoing adjacency lists.
+const OUTGOING: usize = 0;
+/// Index of the incoming adjacency lists.
+const INCOMING: usize = 1;
+
+/// An adjacency list graph data structure.
+///
+/// Allows parallel edges and self-loops.
+///
+/// This data structure is append-only (except for clear), so indices
+/// returned at some point for a given graph will stay valid with this same
+/// graph until it is dropped or clear is called.
+///
+/// Every edge is linked into both the outgoing list of its source and the
+/// incoming list of its target, so children and parents can both be iterated
+/// in **O(degree)**.
+///
+/// Node and edge indices are stored as `Ix`, see [`IndexType`].
+///
+/// Space complexity: **O(|V| + |E|)**
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Graph<E = (), Ix = usize> {
+    /// Maps a node id to the first edge in its outgoing and incoming adjacency lists.
+    first: Vec<[Option<Ix>; 2]>,
+    /// Maps an edge id to the next edge in the same outgoing and incoming adjacency lists.
+    next_edge: Vec<[Option<Ix>; 2]>,
+    /// Maps an edge id to the nodes that it starts from and points to.
+    endpoints: Vec<[Ix; 2]>,
+    /// Maps an edge id to its weight.
+    weights: Vec<E>,
+}
+
+impl<E, Ix: IndexType> Default for Graph<E, Ix> {
+    fn default() -> Self {
+        Self {
+            first: Vec::new(),
+            next_edge: Vec::new(),
+            endpoints: Vec::new(),
+            weights: Vec::new(),
+        }
+    }
+}
+
+impl<E> Graph<E> {
+    /// Constructs an empty graph.
+    ///
+    /// Use [`Graph::default`] to construct a graph with a different index type.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            first: Vec::new(),
+            next_edge: Vec::new(),
+            endpoints: Vec::new(),
+            weights: Vec::new(),
+        }
+    }
+
+    /// Constructs an empty graph with hints for number of vertices and edges
+    /// to reduce unnecessary allocations.
+    #[must_use]
+    pub fn with_capacity(vertices: usize, edges: usize) -> Self {
+        let mut graph = Self::new();
+        graph.reserve(vertices, edges);
+        graph
+    }
+}
+
+impl<E, Ix: IndexType> Graph<E, Ix> {
+    /// Reserves capacity for at least `vertices` more vertices and `edges` more edges.
+    pub fn reserve(&mut self, vertices: usize, edges: usize) {
+        self.first.reserve(vertices);
+        self.next_edge.reserve(edges);
+        self.endpoints.reserve(edges);
+        self.weights.reserve(edges);
+    }
+
+    /// Returns the max number of vertices for the graph.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.first.len()
+    }
+
+    /// Returns true if the graph has no edges.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.edge_count() == 0
+    }
+
+    /// Returns the number of edges in the graph.
+    /// Double-counts undirected edges. Includes parallel edges.
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// Removes all nodes and edges from the graph.
+    pub fn clear(&mut self) {
+        self.first.clear();
+        self.next_edge.clear();
+        self.endpoints.clear();
+        self.weights.clear();
+    }
+
+    /// Adds a node without edges to the graph, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node index doesn't fit in the index type.
+    pub fn add_node(&mut self) -> usize {
+        let node = self.first.len();
+        assert!(node <= Ix::MAX, "index out of range for index type");
+        self.first.push([None, None]);
+        node
+    }
+
+    /// Adds a weighted directed edge to the graph from `from` to `to`.
+    /// Returns the edge index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node or edge index doesn't fit in the index type.
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: E) -> usize {
+        // convert every index before mutating so a panic leaves the graph intact
+        let edge_index = self.endpoints.len();
+        let edge = Ix::new(edge_index);
+        let endpoints = [Ix::new(from), Ix::new(to)];
+
+        // update length of first if necessary
+        let len = self
+            .first
+            .len()
+            .max(from.saturating_add(1))
+            .max(to.saturating_add(1));
+        self.first.resize_with(len, || [None, None]);
+
+        // add the edge to the front of both adjacency lists
+        self.next_edge
+            .push([self.first[from][OUTGOING], self.first[to][INCOMING]]);
+        self.first[from][OUTGOING] = Some(edge);
+        self.first[to][INCOMING] = Some(edge);
+        self.endpoints.push(endpoints);
+        self.weights.push(weight);
+        edge_index
+    }
+
+    /// Adds a weighted directed edge to the graph, like [`Graph::add_edge`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::CapacityOverflow`] if a node or edge index doesn't fit
+    /// in the index type.
+    pub fn try_add_edge(&mut self, from: usize, to: usize, weight: E) -> Result<usize, Error> {
+        if [from, to, self.endpoints.len()]
+            .into_iter()
+            .any(|index| Ix::try_new(index).is_none())
+        {
+            return Err(Error::CapacityOverflow);
+        }
+        Ok(self.add_edge(from, to, weight))
+    }
+
+    /// Returns the weight of an edge, or `None` if the edge doesn't exist.
+    #[must_use]
+    pub fn edge_weight(&self, edge: usize) -> Option<&E> {
+        self.weights.get(edge)
+    }
+
+    /// Returns a mutable reference to the weight of an edge, or `None` if the
+    /// edge doesn't exist.
+    pub fn edge_weight_mut(&mut self, edge: usize) -> Option<&mut E> {
+        self.weights.get_mut(edge)
+    }
+
+    /// Returns the `(from, to)` nodes of an edge, or `None` if the edge doesn't exist.
+    #[must_use]
+    pub fn edge_endpoints(&self, edge: usize) -> Option<(usize, usize)> {
+        self.endpoints
+            .get(edge)
+            .map(|&[from, to]| (from.index(), to.index()))
+    }
+
+    /// Returns an iterator of all node-edge tuples with an edge starting from `node`.
+    /// Produces an empty iterator if `node` doesn't exist.
+    #[must_use]
+    pub fn neighbors(&self, node: usize) -> NeighborIterator<'_, E, Ix> {
+        self.adjacent(node, OUTGOING)
+    }
+
+    /// Returns an iterator of all node-edge tuples with an edge ending at `node`.
+    /// Produces an empty iterator if `node` doesn't exist.
+    #[must_use]
+    pub fn neighbors_incoming(&self, node: usize) -> NeighborIterator<'_, E, Ix> {
+        self.adjacent(node, INCOMING)
+    }
+
+    /// Returns an iterator over the adjacency list of `node` in the given direction.
+    fn adjacent(&self, node: usize, direction: usize) -> NeighborIterator<'_, E, Ix> {
+        NeighborIterator {
+            graph: self,
+            direction,
+            next_edge: self.first.get(node).and_then(|first| first[direction]),
+        }
+    }
+
+    /// Returns an iterator over all edges in the graph.
+    /// Does not return them in insertion order.
+    #[must_use]
+    pub fn edges(&self) -> EdgesIterator<'_, E, Ix> {
+        EdgesIterator {
+            graph: self,
+            parent: 0, // start from zero and go to len()
+            neighbors: self.neighbors(0),
+        }
+    }
+}
+
+impl<Ix: IndexType> Graph<(), Ix> {
+    /// Adds a directed edge to the graph from `from` to `to`. Returns the edge index.
+    pub fn push(&mut self, from: usize, to: usize) -> usize {
+        self.add_edge(from, to, ())
+    }
+
+    /// Attaches weights to the edges of the graph, where `weights[i]` is the
+    /// weight of edge `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of weights doesn't match the number of edges.
+    #[must_use]
+    pub fn with_weights<E>(self, weights: Vec<E>) -> Graph<E, Ix> {
+        let (expected, found) = (self.edge_count(), weights.len());
+        assert_eq!(expected, found, "expected {expected} edge weights");
+        Graph {
+            first: self.first,
+            next_edge: self.next_edge,
+            endpoints: self.endpoints,
+            weights,
+        }
+    }
+
+    /// Attaches weights to the edges of the graph, like [`Graph::with_weights`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::WeightLengthMismatch`] if the number of weights doesn't
+    /// match the number of edges.
+    pub fn try_with_weights<E>(self, weights: Vec<E>) -> Result<Graph<E, Ix>, Error> {
+        let (expected, found) = (self.edge_count(), weights.len());
+        if expected != found {
+            return Err(Error::WeightLengthMismatch { expected, found });
+        }
+        Ok(self.with_weights(weights))
+    }
+}
+
+impl<E: Clone, Ix: IndexType> Graph<E, Ix> {
+    /// Returns a transposed version of the graph.
+    /// <https://en.wikipedia.org/wiki/Transpose_graph>
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let mut graph = Self::default();
+        graph.reserve(self.len(), self.edge_count());
+        for parent in 0..self.len() {
+            for (child, edge) in self.neighbors(parent) {
+                graph.add_edge(child, parent, self.weights[edge].clone());
+            }
+        }
+        graph
+    }
+}
+
+/// An iterator for all edges in the graph.
+#[derive(Debug, Clone)]
+pub struct EdgesIterator<'graph, E, Ix = usize> {
+    /// The graph that this iterator is iterating over.
+    graph: &'graph Graph<E, Ix>,
+    /// The current parent vertex.
+    parent: usize,
+    /// The current neighbor iterator.
+    neighbors: NeighborIterator<'graph, E, Ix>,
+}
+
+impl<E, Ix: IndexType> Iterator for EdgesIterator<'_, E, Ix> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((neighbor, _)) = self.neighbors.next() {
+                return Some((self.parent, neighbor));
+            }
+            self.parent = self.parent.saturating_add(1);
+            if self.parent >= self.graph.len() {
+                return None;
+            }
+            self.neighbors = self.graph.neighbors(self.parent);
+        }
+    }
+}
+
+/// An iterator for convenient adjacency list traversal.
+#[derive(Debug)]
+pub struct NeighborIterator<'graph, E, Ix = usize> {
+    /// The graph that this iterator is iterating over.
+    graph: &'graph Graph<E, Ix>,
+    /// Whether the outgoing or incoming adjacency list is followed.
+    direction: usize,
+    /// The next edge in the adjacency list.
+    next_edge: Option<Ix>,
+}
+
+impl<E, Ix: Clone> Clone for NeighborIterator<'_, E, Ix> {
+    fn clone(&self) -> Self {
+        Self {
+            graph: self.graph,
+            direction: self.direction,
+            next_edge: self.next_edge.clone(),
+        }
+    }
+}
+
+impl<E, Ix: IndexType> Iterator for NeighborIterator<'_, E, Ix> {
+    type Item = (usize, usize);
+
+    /// Produces an adjacent vertex and the edge connecting it.
+    fn next(&mut self) -> Option<Self::Item> {
+        let next_edge = self.next_edge?.index();
+        // the neighbor is at the opposite end of the edge
+        let v = self.graph.endpoints[next_edge][1 - self.direction];
+        self.next_edge = self.graph.next_edge[next_edge][self.direction];
+        Some((v.index(), next_edge))
+    }
+}
+
+/// Iterator over the children or parents of a node.
+#[derive(Debug, Clone)]
+pub struct Neighbors<'graph, E, Ix = usize> {
+    /// The underlying adjacency list iterator.
+    iter: NeighborIterator<'graph, E, Ix>,
+}
+
+impl<E, Ix: IndexType> Iterator for Neighbors<'_, E, Ix> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(node, _)| node)
+    }
+}
+
+/// Iterator over the edges from or to a node.
+#[derive(Debug, Clone)]
+pub struct Edges<'graph, E, Ix = usize> {
+    /// The underlying adjacency list iterator.
+    iter: NeighborIterator<'graph, E, Ix>,
+}
+
+impl<'graph, E, Ix: IndexType> Iterator for Edges<'graph, E, Ix> {
+    type Item = (usize, &'graph E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, edge) = self.iter.next()?;
+        Some((node, &self.iter.graph.weights[edge]))
+    }
+}
+
+impl<'graph, E, Ix: IndexType> Children for &'graph Graph<E, Ix> {
+    type Iter = Neighbors<'graph, E, Ix>;
+
+    fn children(self, node: usize) -> Neighbors<'graph, E, Ix> {
+        Neighbors {
+            iter: self.neighbors(node),
+        }
+    }
+}
+
+impl<'graph, E, Ix: IndexType> Parents for &'graph Graph<E, Ix> {
+    type Iter = Neighbors<'graph, E, Ix>;
+
+    fn parents(self, node: usize) -> Neighbors<'graph, E, Ix> {
+        Neighbors {
+            iter: self.neighbors_incoming(node),
+        }
+    }
+}
+
+impl<E, Ix: IndexType> NodeCount for &Graph<E, Ix> {
+    fn node_count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<'graph, E, Ix: IndexType> Outgoing<&'graph E> for &'graph Graph<E, Ix> {
+    type Iter = Edges<'graph, E, Ix>;
+
+    fn outgoing(self, node: usize) -> Edges<'graph, E, Ix> {
+        Edges {
+            iter: self.neighbors(node),
+        }
+    }
+}
+
+impl<'graph, E, Ix: IndexType> Incoming<&'graph E> for &'graph Graph<E, Ix> {
+    type Iter = Edges<'graph, E, Ix>;
+
+    fn incoming(self, node: usize) -> Edges<'graph, E, Ix> {
+        Edges {
+            iter: self.neighbors_incoming(node),
+        }
+    }
+}
+
+impl<Ix: IndexType> Extend<(usize, usize)> for Graph<(), Ix> {
+    fn extend<T: IntoIterator<Item = (usize, usize)>>(&mut self, iter: T) {
+        for (from, to) in iter {
+            self.push(from, to);
+        }
+    }
+}
+
+impl<E, Ix: IndexType> Extend<(usize, usize, E)> for Graph<E, Ix> {
+    fn extend<T: IntoIterator<Item = (usize, usize, E)>>(&mut self, iter: T) {
+        for (from, to, weight) in iter {
+            self.add_edge(from, to, weight);
+        }
+    }
+}
+
+impl<const N: usize> From<[(usize, usize); N]> for Graph {
+    /// Constructs a graph from an array of edges.
+    fn from(edges: [(usize, usize); N]) -> Self {
+        let mut graph = Self::new();
+        for (from, to) in edges {
+            graph.push(from, to);
+        }
+        graph
+    }
+}
+
+impl<const N: usize, E> From<[(usize, usize, E); N]> for Graph<E> {
+    /// Constructs a weighted graph from an array of edges.
+    fn from(edges: [(usize, usize, E); N]) -> Self {
+        let mut graph = Self::new();
+        graph.extend(edges);
+        graph
+    }
+}
+
+impl From<&[(usize, usize)]> for Graph {
+    /// Constructs a graph from a slice of edges.
+    fn from(edges: &[(usize, usize)]) -> Self {
+        let mut graph = Self::new();
+        for &(from, to) in edges {
+            graph.push(from, to);
+        }
+        graph
+    }
+}
+
+impl<Ix: IndexType> FromIterator<(usize, usize)> for Graph<(), Ix> {
+    fn from_iter<T: IntoIterator<Item = (usize, usize)>>(iter: T) -> Self {
+        let mut graph = Self::default();
+        graph.extend(iter);
+        graph
+    }
+}
+
+impl<E, Ix: IndexType> FromIterator<(usize, usize, E)> for Graph<E, Ix> {
+    fn from_iter<T: IntoIterator<Item = (usize, usize, E)>>(iter: T) -> Self {
+        let mut graph = Self::default();
+        graph.extend(iter);
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        graph::index::NonMaxU32,
+        traversal::{preorder::PreOrder, topological::Topological},
+    };
+
+    #[test]
+    fn test_size() {
+        let mut graph = Graph::new();
+        assert!(graph.is_empty());
+        assert_eq!(graph.len(), 0);
+        assert_eq!(graph.push(0, 1), 0);
+        assert_eq!(graph.push(0, 1), 1);
+        assert_eq!(graph.push(1, 1), 2);
+        assert_eq!(graph.push(1, 0), 3);
+        assert!(!graph.is_empty());
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.edge_count(), 4);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let graph = Graph::from([(2, 3), (2, 4), (4, 1), (1, 2)]);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), [(4, 1), (3, 0)]);
+        assert_eq!(graph.neighbors_incoming(2).collect::<Vec<_>>(), [(1, 3)]);
+
+        let edges = graph.edges().collect::<Vec<_>>();
+        assert_eq!(edges, [(1, 2), (2, 4), (2, 3), (4, 1)]);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let graph = Graph::from([(2, 3), (2, 4), (1, 3)]);
+        assert_eq!(graph.neighbors(2).collect::<Vec<_>>(), [(4, 1), (3, 0)]);
+        let transpose = graph.transpose();
+        assert_eq!(transpose.neighbors(3).collect::<Vec<_>>(), [(2, 2), (1, 0)]);
+    }
+
+    #[test]
+    fn test_traits() {
+        let graph = Graph::from([(0, 1, 'a'), (0, 2, 'b'), (2, 1, 'c')]);
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.children(0).collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(graph.parents(1).collect::<Vec<_>>(), [2, 0]);
+        assert_eq!(
+            graph.outgoing(0).collect::<Vec<_>>(),
+            [(2, &'b'), (1, &'a')]
+        );
+        assert_eq!(
+            graph.incoming(1).collect::<Vec<_>>(),
+            [(2, &'c'), (0, &'a')]
+        );
+    }
+
+    #[test]
+    fn test_index_type() {
+        let graph: Graph<(), NonMaxU32> = [(0, 2), (0, 1), (1, 3)].into_iter().collect();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.children(0).collect::<Vec<_>>(), [1, 2]);
+        let order = PreOrder::<_, u32>::with_index_type(&graph, 0).collect::<Vec<_>>();
+        assert_eq!(order, [0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let graph = Graph::from([(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(
+            graph.clone().try_with_weights(vec![7, 3]),
+            Err(Error::WeightLengthMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+        let graph = graph.with_weights(vec![7, 3, 5]);
+        assert_eq!(graph.edge_weight(1), Some(&3));
+
+        let mut graph = Graph::<(), u16>::default();
+        assert_eq!(graph.try_add_edge(0, 1, ()), Ok(0));
+        assert_eq!(
+            graph.try_add_edge(0, usize::from(u16::MAX) + 1, ()),
+            Err(Error::CapacityOverflow)
+        );
+        assert_eq!(graph.edge_count(), 1);
+
+        // a panicking add_edge leaves the graph unchanged
+        let added = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            graph.add_edge(0, usize::from(u16::MAX) + 1, ())
+        }));
+        assert!(added.is_err());
+        assert_eq!((graph.len(), graph.edge_count()), (2, 1));
+    }
+
+    #[test]
+    fn test_parallel_edges() {
+        // new edges go to the front of the adjacency lists
+        let mut graph = Graph::from([(0, 1), (0, 2), (0, 1)]);
+        assert_eq!(graph.children(0).collect::<Vec<_>>(), [1, 2, 1]);
+        assert_eq!(graph.parents(1).collect::<Vec<_>>(), [0, 0]);
+        assert_eq!(graph.children(7).count(), 0);
+        assert_eq!(graph.add_node(), 3);
+        assert_eq!(graph.node_count(), 4);
+
+        // both copies count towards the in-degree, and the order of the
+        // children decides between the nodes that are ready
+        let order = Topological::new(&graph).collect::<Vec<_>>();
+        assert_eq!(order, [3, 0, 1, 2]);
+        let order = PreOrder::new(&graph, 0).collect::<Vec<_>>();
+        assert_eq!(order, [0, 2, 1]);
+    }
+}
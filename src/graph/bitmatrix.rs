@@ -0,0 +1,371 @@
+//! Bit-packed adjacency matrix graph implementation for unweighted graphs.
+use core::{marker::PhantomData, ops::Range};
+
+use super::{
+    traits::{Children, Directed, Direction, NodeCount, Parents},
+    util::to_linear_matrix_position,
+};
+
+/// The number of bits in a word of the matrix.
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// An unweighted graph represented using an adjacency matrix with one bit per cell.
+///
+/// Directed graphs use a square matrix, undirected graphs a lower triangular one.
+/// Children of a node in a directed graph are found by scanning the set bits
+/// of its row with `trailing_zeros`, so the cost depends on the number of edges
+/// rather than the number of nodes. Parents require a scan of a column.
+///
+/// Space complexity: **O(|V|^2 / 64)**
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitMatrix<Ty = Directed> {
+    /// The bits of the linearized adjacency matrix.
+    bits: Vec<u64>,
+    /// The width of the matrix, i.e. the number of nodes that can be stored in
+    /// the graph without reallocating.
+    capacity: usize,
+    /// The number of nodes in the graph.
+    n_nodes: usize,
+    /// The number of edges in the graph.
+    n_edges: usize,
+    /// Whether the graph is directed or undirected.
+    ty: PhantomData<Ty>,
+}
+
+impl<Ty: Direction> Default for BitMatrix<Ty> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the number of words needed to store a matrix with `capacity` nodes.
+fn word_count<Ty: Direction>(capacity: usize) -> usize {
+    if capacity == 0 {
+        0
+    } else {
+        let n_bits = to_linear_matrix_position::<Ty>(capacity - 1, capacity - 1, capacity) + 1;
+        n_bits.div_ceil(WORD_BITS)
+    }
+}
+
+impl<Ty: Direction> BitMatrix<Ty> {
+    /// Constructs a new graph.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bits: Vec::new(),
+            capacity: 0,
+            n_nodes: 0,
+            n_edges: 0,
+            ty: PhantomData,
+        }
+    }
+
+    /// Creates a new graph with the specified number of nodes.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            bits: vec![0; word_count::<Ty>(capacity)],
+            capacity,
+            n_nodes: capacity,
+            n_edges: 0,
+            ty: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the graph contains no edges.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.n_edges == 0
+    }
+
+    /// Returns the number of edges in the graph.
+    #[must_use]
+    pub const fn edge_count(&self) -> usize {
+        self.n_edges
+    }
+
+    /// Adds an edge from `a` to `b` to the graph.
+    /// Nodes that don't exist yet are added to the graph.
+    ///
+    /// Returns `true` if the edge didn't exist before.
+    pub fn add_edge(&mut self, a: usize, b: usize) -> bool {
+        let max_node = (a + 1).max(b + 1);
+        if max_node > self.capacity {
+            self.grow(max_node.max(self.capacity * 2));
+        }
+        self.n_nodes = self.n_nodes.max(max_node);
+
+        let (word, mask) = self.bit(a, b);
+        let is_new = self.bits[word] & mask == 0;
+        self.bits[word] |= mask;
+        if is_new {
+            self.n_edges += 1;
+        }
+        is_new
+    }
+
+    /// Removes the edge from `a` to `b`.
+    /// Returns `true` if the edge existed.
+    pub fn remove_edge(&mut self, a: usize, b: usize) -> bool {
+        if !self.contains_edge(a, b) {
+            return false;
+        }
+        let (word, mask) = self.bit(a, b);
+        self.bits[word] &= !mask;
+        self.n_edges -= 1;
+        true
+    }
+
+    /// Returns `true` if there is an edge from `a` to `b`.
+    #[must_use]
+    pub fn contains_edge(&self, a: usize, b: usize) -> bool {
+        if a >= self.n_nodes || b >= self.n_nodes {
+            return false;
+        }
+        let (word, mask) = self.bit(a, b);
+        self.bits[word] & mask != 0
+    }
+
+    /// Returns the word index and bit mask of a cell in the matrix.
+    fn bit(&self, row: usize, column: usize) -> (usize, u64) {
+        let position = to_linear_matrix_position::<Ty>(row, column, self.capacity);
+        (position / WORD_BITS, 1 << (position % WORD_BITS))
+    }
+
+    /// Reallocates the matrix to hold `capacity` nodes.
+    fn grow(&mut self, capacity: usize) {
+        let mut bits = vec![0; word_count::<Ty>(capacity)];
+        for row in 0..self.n_nodes {
+            for column in self.children(row) {
+                let position = to_linear_matrix_position::<Ty>(row, column, capacity);
+                bits[position / WORD_BITS] |= 1 << (position % WORD_BITS);
+            }
+        }
+        self.bits = bits;
+        self.capacity = capacity;
+    }
+}
+
+/// Constructs a graph from an array of edges.
+impl<const N: usize, Ty: Direction> From<[(usize, usize); N]> for BitMatrix<Ty> {
+    fn from(edges: [(usize, usize); N]) -> Self {
+        Self::from_iter(edges)
+    }
+}
+
+impl<Ty: Direction> FromIterator<(usize, usize)> for BitMatrix<Ty> {
+    fn from_iter<I: IntoIterator<Item = (usize, usize)>>(iter: I) -> Self {
+        let mut graph = Self::new();
+        for (from, to) in iter {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+}
+
+/// Iterator over the positions of the set bits in a range of a bit vector.
+#[derive(Debug, Clone)]
+struct Ones<'graph> {
+    /// The words of the bit vector.
+    bits: &'graph [u64],
+    /// The index of the word being scanned.
+    word: usize,
+    /// The unvisited set bits of the current word.
+    current: u64,
+    /// The bit position where the range ends.
+    end: usize,
+}
+
+impl<'graph> Ones<'graph> {
+    /// Creates an iterator over the set bits in `range`.
+    fn new(bits: &'graph [u64], range: Range<usize>) -> Self {
+        let word = range.start / WORD_BITS;
+        let current = if range.is_empty() {
+            0
+        } else {
+            bits[word] & (u64::MAX << (range.start % WORD_BITS))
+        };
+        Self {
+            bits,
+            word,
+            current,
+            end: range.end,
+        }
+    }
+}
+
+impl Iterator for Ones<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.word += 1;
+            if self.word * WORD_BITS >= self.end {
+                return None;
+            }
+            self.current = self.bits[self.word];
+        }
+        let position = self.word * WORD_BITS + self.current.trailing_zeros() as usize;
+        if position >= self.end {
+            self.current = 0;
+            return None;
+        }
+        // clear the lowest set bit
+        self.current &= self.current - 1;
+        Some(position)
+    }
+}
+
+/// Iterator over the children or parents of a node.
+#[derive(Debug, Clone)]
+pub struct Neighbors<'graph, Ty> {
+    /// The set bits of the part of the node's row stored contiguously.
+    row: Ones<'graph>,
+    /// The position in the matrix of the node's first neighbor in `row`.
+    row_start: usize,
+    /// The rows that still have to be checked for the node's column.
+    rows: Range<usize>,
+    /// The node whose column is scanned.
+    column: usize,
+    /// The width of the matrix.
+    capacity: usize,
+    /// Whether the graph is directed or undirected.
+    ty: PhantomData<Ty>,
+}
+
+impl<Ty: Direction> Iterator for Neighbors<'_, Ty> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if let Some(position) = self.row.next() {
+            return Some(position - self.row_start);
+        }
+        for row in self.rows.by_ref() {
+            let position = to_linear_matrix_position::<Ty>(row, self.column, self.capacity);
+            if self.row.bits[position / WORD_BITS] & (1 << (position % WORD_BITS)) != 0 {
+                return Some(row);
+            }
+        }
+        None
+    }
+}
+
+impl<Ty: Direction> BitMatrix<Ty> {
+    /// Returns an iterator over the set bits in `row` followed by the rows
+    /// in `rows` that have a set bit in column `column`.
+    fn neighbors(&self, row: Range<usize>, rows: Range<usize>, column: usize) -> Neighbors<'_, Ty> {
+        Neighbors {
+            row_start: row.start,
+            row: Ones::new(&self.bits, row),
+            rows,
+            column,
+            capacity: self.capacity,
+            ty: PhantomData,
+        }
+    }
+
+    /// Returns the neighbors of an undirected node: the columns of its part of
+    /// the lower triangular matrix, then the rows below it.
+    fn undirected_neighbors(&self, node: usize) -> Neighbors<'_, Ty> {
+        if node >= self.n_nodes {
+            return self.neighbors(0..0, 0..0, node);
+        }
+        let start = to_linear_matrix_position::<Ty>(node, 0, self.capacity);
+        self.neighbors(start..start + node + 1, node + 1..self.n_nodes, node)
+    }
+}
+
+impl<'graph, Ty: Direction> Children for &'graph BitMatrix<Ty> {
+    type Iter = Neighbors<'graph, Ty>;
+
+    fn children(self, node: usize) -> Neighbors<'graph, Ty> {
+        if !Ty::is_directed() {
+            return self.undirected_neighbors(node);
+        }
+        if node >= self.n_nodes {
+            return self.neighbors(0..0, 0..0, node);
+        }
+        let start = node * self.capacity;
+        self.neighbors(start..start + self.n_nodes, 0..0, node)
+    }
+}
+
+impl<'graph, Ty: Direction> Parents for &'graph BitMatrix<Ty> {
+    type Iter = Neighbors<'graph, Ty>;
+
+    fn parents(self, node: usize) -> Neighbors<'graph, Ty> {
+        if !Ty::is_directed() {
+            return self.undirected_neighbors(node);
+        }
+        let rows = if node < self.n_nodes {
+            0..self.n_nodes
+        } else {
+            0..0
+        };
+        self.neighbors(0..0, rows, node)
+    }
+}
+
+impl<Ty> NodeCount for &BitMatrix<Ty> {
+    fn node_count(self) -> usize {
+        self.n_nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitMatrix;
+    use crate::{
+        graph::traits::{Children, Directed, NodeCount, Parents, Undirected},
+        traversal::{preorder::PreOrder, topological::Topological},
+    };
+
+    #[test]
+    fn test_directed() {
+        let mut graph = BitMatrix::<Directed>::new();
+        assert!(graph.add_edge(0, 1));
+        assert!(graph.add_edge(0, 70));
+        assert!(graph.add_edge(70, 0));
+        assert!(graph.add_edge(3, 3));
+        assert!(!graph.add_edge(0, 1));
+        assert_eq!(graph.node_count(), 71);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.children(0).collect::<Vec<_>>(), [1, 70]);
+        assert_eq!(graph.parents(0).collect::<Vec<_>>(), [70]);
+        assert_eq!(graph.children(3).collect::<Vec<_>>(), [3]);
+        assert_eq!(graph.children(100).count(), 0);
+        assert!(graph.contains_edge(70, 0));
+        assert!(graph.remove_edge(70, 0));
+        assert!(!graph.remove_edge(70, 0));
+        assert!(!graph.contains_edge(70, 0));
+        assert_eq!(graph.edge_count(), 3);
+    }
+
+    #[test]
+    fn test_undirected() {
+        let graph = BitMatrix::<Undirected>::from([(0, 1), (2, 1), (1, 1), (80, 1)]);
+        assert_eq!(graph.children(1).collect::<Vec<_>>(), [0, 1, 2, 80]);
+        assert_eq!(graph.parents(1).collect::<Vec<_>>(), [0, 1, 2, 80]);
+        assert_eq!(graph.children(80).collect::<Vec<_>>(), [1]);
+        assert!(graph.contains_edge(1, 2));
+        assert!(graph.contains_edge(2, 1));
+        assert_eq!(graph.edge_count(), 4);
+    }
+
+    #[test]
+    fn test_ordering() {
+        // children come out in ascending order whatever the insertion order,
+        // and adding an edge twice keeps a single copy
+        let graph = BitMatrix::<Directed>::from([(0, 3), (0, 1), (0, 2), (0, 1), (2, 1)]);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.children(0).collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(graph.parents(1).collect::<Vec<_>>(), [0, 2]);
+
+        // so traversals don't depend on how the graph was built
+        let order = PreOrder::new(&graph, 0).collect::<Vec<_>>();
+        assert_eq!(order, [0, 3, 2, 1]);
+        let order = Topological::new(&graph).collect::<Vec<_>>();
+        assert_eq!(order, [0, 3, 2, 1]);
+    }
+}
@@ -0,0 +1,353 @@
+//! Compressed sparse row graph implementation.
+//! <https://en.wikipedia.org/wiki/Sparse_matrix#Compressed_sparse_row_(CSR,_CRS_or_Yale_format)>
+use core::slice::Iter;
+
+use super::{
+    index::IndexType,
+    traits::{Children, Incoming, NodeCount, Outgoing, Parents},
+};
+
+/// An immutable graph in compressed sparse row format.
+///
+/// The edges of each node are stored contiguously, so iterating over the
+/// children or parents of a node is a linear scan of a slice. A reverse copy
+/// of the adjacency structure is built eagerly to support parent iteration.
+///
+/// Allows parallel edges and self-loops. Edges leaving the same node keep
+/// their insertion order.
+///
+/// Node and edge indices are stored as `Ix`, see [`IndexType`].
+///
+/// Space complexity: **O(|V| + |E|)**
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Graph<E = (), Ix = usize> {
+    /// Maps a node to the start of its edges in `targets`.
+    /// Has one more entry than there are nodes.
+    offsets: Vec<usize>,
+    /// Maps an edge id to the node that it points to.
+    targets: Vec<Ix>,
+    /// Maps an edge id to its weight.
+    weights: Vec<E>,
+    /// Maps a node to the start of its incoming edges in `sources`.
+    /// Has one more entry than there are nodes.
+    reverse_offsets: Vec<usize>,
+    /// The nodes that incoming edges start from, grouped by target.
+    sources: Vec<Ix>,
+    /// Maps a position in `sources` to the edge id.
+    reverse_edges: Vec<Ix>,
+}
+
+impl<E, Ix: IndexType> Graph<E, Ix> {
+    /// Constructs a graph from an iterator of weighted edges.
+    ///
+    /// The number of nodes is one more than the largest node in `edges`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node or edge index doesn't fit in the index type.
+    ///
+    /// Time complexity: **O(|V| + |E| log |E|)**
+    #[must_use]
+    pub fn from_edges<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        Self::with_node_count(0, edges)
+    }
+
+    /// Constructs a graph with at least `node_count` nodes from an iterator of
+    /// weighted edges. Nodes without edges can only be added this way.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node or edge index doesn't fit in the index type.
+    ///
+    /// Time complexity: **O(|V| + |E| log |E|)**
+    #[must_use]
+    pub fn with_node_count<I>(node_count: usize, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, E)>,
+    {
+        let mut edges = edges.into_iter().collect::<Vec<_>>();
+        // stable sort keeps edges from the same node in insertion order
+        edges.sort_by_key(|&(from, _, _)| from);
+
+        let n_nodes = edges
+            .iter()
+            .map(|&(from, to, _)| from.max(to) + 1)
+            .max()
+            .unwrap_or(0)
+            .max(node_count);
+
+        let mut offsets = vec![0; n_nodes + 1];
+        let mut reverse_offsets = vec![0; n_nodes + 1];
+        for &(from, to, _) in &edges {
+            offsets[from + 1] += 1;
+            reverse_offsets[to + 1] += 1;
+        }
+        for i in 0..n_nodes {
+            offsets[i + 1] += offsets[i];
+            reverse_offsets[i + 1] += reverse_offsets[i];
+        }
+
+        let mut targets = Vec::with_capacity(edges.len());
+        let mut weights = Vec::with_capacity(edges.len());
+        let mut sources = vec![Ix::new(0); edges.len()];
+        let mut reverse_edges = vec![Ix::new(0); edges.len()];
+        // the next free position in `sources` for each node
+        let mut cursor = reverse_offsets.clone();
+        for (edge, (from, to, weight)) in edges.into_iter().enumerate() {
+            targets.push(Ix::new(to));
+            weights.push(weight);
+            sources[cursor[to]] = Ix::new(from);
+            reverse_edges[cursor[to]] = Ix::new(edge);
+            cursor[to] += 1;
+        }
+
+        Self {
+            offsets,
+            targets,
+            weights,
+            reverse_offsets,
+            sources,
+            reverse_edges,
+        }
+    }
+
+    /// Returns the number of nodes in the graph.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Returns `true` if the graph contains no edges.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Returns the number of edges in the graph. Includes parallel edges.
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns the range of edge ids starting from `node`.
+    /// The range is empty if `node` doesn't exist.
+    fn edge_range(&self, node: usize) -> core::ops::Range<usize> {
+        match (self.offsets.get(node), self.offsets.get(node + 1)) {
+            (Some(&start), Some(&end)) => start..end,
+            _ => 0..0,
+        }
+    }
+
+    /// Returns the range of positions in `sources` of edges ending at `node`.
+    /// The range is empty if `node` doesn't exist.
+    fn reverse_edge_range(&self, node: usize) -> core::ops::Range<usize> {
+        match (
+            self.reverse_offsets.get(node),
+            self.reverse_offsets.get(node + 1),
+        ) {
+            (Some(&start), Some(&end)) => start..end,
+            _ => 0..0,
+        }
+    }
+}
+
+impl<E, Ix: IndexType> Default for Graph<E, Ix> {
+    fn default() -> Self {
+        Self::from_edges([])
+    }
+}
+
+/// Constructs a weighted graph from an array of edges.
+impl<const N: usize, E> From<[(usize, usize, E); N]> for Graph<E> {
+    fn from(edges: [(usize, usize, E); N]) -> Self {
+        Self::from_edges(edges)
+    }
+}
+
+/// Constructs an unweighted graph from an array of edges.
+impl<const N: usize> From<[(usize, usize); N]> for Graph {
+    fn from(edges: [(usize, usize); N]) -> Self {
+        Self::from_iter(edges)
+    }
+}
+
+impl<E, Ix: IndexType> FromIterator<(usize, usize, E)> for Graph<E, Ix> {
+    fn from_iter<I: IntoIterator<Item = (usize, usize, E)>>(iter: I) -> Self {
+        Self::from_edges(iter)
+    }
+}
+
+impl<Ix: IndexType> FromIterator<(usize, usize)> for Graph<(), Ix> {
+    fn from_iter<I: IntoIterator<Item = (usize, usize)>>(iter: I) -> Self {
+        Self::from_edges(iter.into_iter().map(|(from, to)| (from, to, ())))
+    }
+}
+
+/// Iterator over the children or parents of a node.
+#[derive(Debug, Clone)]
+pub struct Neighbors<'graph, Ix = usize> {
+    /// The neighboring nodes.
+    nodes: Iter<'graph, Ix>,
+}
+
+impl<Ix: IndexType> Iterator for Neighbors<'_, Ix> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.nodes.next().map(|node| node.index())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+/// Iterator over the outgoing edges of a node.
+#[derive(Debug, Clone)]
+pub struct Edges<'graph, E, Ix = usize> {
+    /// The nodes the edges point to.
+    targets: Iter<'graph, Ix>,
+    /// The weights of the edges.
+    weights: Iter<'graph, E>,
+}
+
+impl<'graph, E, Ix: IndexType> Iterator for Edges<'graph, E, Ix> {
+    type Item = (usize, &'graph E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((self.targets.next()?.index(), self.weights.next()?))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.targets.size_hint()
+    }
+}
+
+/// Iterator over the incoming edges of a node.
+#[derive(Debug, Clone)]
+pub struct IncomingEdges<'graph, E, Ix = usize> {
+    /// The nodes the edges start from.
+    sources: Iter<'graph, Ix>,
+    /// The ids of the edges.
+    edges: Iter<'graph, Ix>,
+    /// The weights of all edges in the graph.
+    weights: &'graph [E],
+}
+
+impl<'graph, E, Ix: IndexType> Iterator for IncomingEdges<'graph, E, Ix> {
+    type Item = (usize, &'graph E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((
+            self.sources.next()?.index(),
+            &self.weights[self.edges.next()?.index()],
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.sources.size_hint()
+    }
+}
+
+impl<'graph, E, Ix: IndexType> Children for &'graph Graph<E, Ix> {
+    type Iter = Neighbors<'graph, Ix>;
+
+    fn children(self, node: usize) -> Neighbors<'graph, Ix> {
+        Neighbors {
+            nodes: self.targets[self.edge_range(node)].iter(),
+        }
+    }
+}
+
+impl<'graph, E, Ix: IndexType> Parents for &'graph Graph<E, Ix> {
+    type Iter = Neighbors<'graph, Ix>;
+
+    fn parents(self, node: usize) -> Neighbors<'graph, Ix> {
+        Neighbors {
+            nodes: self.sources[self.reverse_edge_range(node)].iter(),
+        }
+    }
+}
+
+impl<E, Ix: IndexType> NodeCount for &Graph<E, Ix> {
+    fn node_count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<'graph, E, Ix: IndexType> Outgoing<&'graph E> for &'graph Graph<E, Ix> {
+    type Iter = Edges<'graph, E, Ix>;
+
+    fn outgoing(self, node: usize) -> Edges<'graph, E, Ix> {
+        let range = self.edge_range(node);
+        Edges {
+            targets: self.targets[range.clone()].iter(),
+            weights: self.weights[range].iter(),
+        }
+    }
+}
+
+impl<'graph, E, Ix: IndexType> Incoming<&'graph E> for &'graph Graph<E, Ix> {
+    type Iter = IncomingEdges<'graph, E, Ix>;
+
+    fn incoming(self, node: usize) -> IncomingEdges<'graph, E, Ix> {
+        let range = self.reverse_edge_range(node);
+        IncomingEdges {
+            sources: self.sources[range.clone()].iter(),
+            edges: self.reverse_edges[range].iter(),
+            weights: &self.weights,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Graph;
+    use crate::{
+        graph::traits::{Children, Incoming, NodeCount, Outgoing, Parents},
+        traversal::topological::Topological,
+    };
+
+    #[test]
+    fn test_csr() {
+        let graph = Graph::from([(2, 3, 'a'), (0, 2, 'b'), (2, 1, 'c'), (1, 2, 'd')]);
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.children(2).collect::<Vec<_>>(), [3, 1]);
+        assert_eq!(graph.children(3).count(), 0);
+        assert_eq!(graph.children(7).count(), 0);
+        assert_eq!(graph.parents(2).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(
+            graph.outgoing(2).collect::<Vec<_>>(),
+            [(3, &'a'), (1, &'c')]
+        );
+        assert_eq!(
+            graph.incoming(2).collect::<Vec<_>>(),
+            [(0, &'b'), (1, &'d')]
+        );
+        assert!(Graph::<()>::default().is_empty());
+        let graph = Graph::<_>::with_node_count(5, [(0, 1, ())]);
+        assert_eq!(graph.node_count(), 5);
+        assert_eq!(graph.parents(4).count(), 0);
+    }
+
+    #[test]
+    fn test_parallel_edges() {
+        // edges are grouped by source, keeping their insertion order
+        let graph = Graph::from([(1, 0, 'a'), (0, 1, 'b'), (1, 1, 'c'), (0, 1, 'd')]);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(
+            graph.outgoing(0).collect::<Vec<_>>(),
+            [(1, &'b'), (1, &'d')]
+        );
+        assert_eq!(graph.children(1).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(graph.parents(1).collect::<Vec<_>>(), [0, 0, 1]);
+
+        // both copies count towards the in-degree
+        let order = Topological::new(&Graph::from([(0, 1), (0, 1), (2, 1)])).collect::<Vec<_>>();
+        assert_eq!(order, [2, 0, 1]);
+    }
+}
@@ -0,0 +1,151 @@
+//! Integer types used to store node and edge indices.
+//!
+//! Graphs and traversals expose indices as `usize`, but can store them in a
+//! smaller type to save memory on large graphs.
+//!
+//! The adjacency list, CSR, stable and map graphs store indices as `Ix`.
+//! The matrix graphs store no indices, only one entry per pair of nodes, so
+//! they don't take an index type.
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::Hash,
+    num::NonZeroU32,
+};
+
+/// A type that node and edge indices can be stored as.
+pub trait IndexType: Copy + Ord + Hash + Debug + 'static {
+    /// The largest index that can be stored.
+    const MAX: usize;
+
+    /// Converts a `usize` index to the index type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is larger than [`IndexType::MAX`].
+    fn new(index: usize) -> Self;
+
+    /// Converts the index to a `usize`.
+    fn index(self) -> usize;
+
+    /// Converts a `usize` index to the index type.
+    /// Returns `None` if `index` is larger than [`IndexType::MAX`].
+    #[must_use]
+    fn try_new(index: usize) -> Option<Self> {
+        (index <= Self::MAX).then(|| Self::new(index))
+    }
+}
+
+impl IndexType for usize {
+    const MAX: usize = Self::MAX;
+
+    #[inline]
+    fn new(index: usize) -> Self {
+        index
+    }
+
+    #[inline]
+    fn index(self) -> usize {
+        self
+    }
+}
+
+/// Implements `IndexType` for an unsigned integer type.
+macro_rules! impl_index_type {
+    ($ty:ty) => {
+        impl IndexType for $ty {
+            const MAX: usize = <$ty>::MAX as usize;
+
+            #[inline]
+            #[allow(clippy::expect_used)]
+            fn new(index: usize) -> Self {
+                <$ty>::try_from(index).expect("index out of range for index type")
+            }
+
+            #[inline]
+            fn index(self) -> usize {
+                self as usize
+            }
+        }
+    };
+}
+
+impl_index_type!(u16);
+impl_index_type!(u32);
+
+/// A `u32` that can't be `u32::MAX`.
+///
+/// The missing value lets `Option<NonMaxU32>` be the same size as `u32`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NonMaxU32(NonZeroU32);
+
+impl NonMaxU32 {
+    /// Creates a `NonMaxU32` if the value isn't `u32::MAX`.
+    #[must_use]
+    pub const fn new(value: u32) -> Option<Self> {
+        match NonZeroU32::new(value ^ u32::MAX) {
+            Some(inner) => Some(Self(inner)),
+            None => None,
+        }
+    }
+
+    /// Returns the value as a `u32`.
+    #[must_use]
+    pub const fn get(self) -> u32 {
+        self.0.get() ^ u32::MAX
+    }
+}
+
+impl Ord for NonMaxU32 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.get().cmp(&other.get())
+    }
+}
+
+impl PartialOrd for NonMaxU32 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Debug for NonMaxU32 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl IndexType for NonMaxU32 {
+    const MAX: usize = (u32::MAX - 1) as usize;
+
+    #[inline]
+    #[allow(clippy::expect_used)]
+    fn new(index: usize) -> Self {
+        u32::try_from(index)
+            .ok()
+            .and_then(Self::new)
+            .expect("index out of range for index type")
+    }
+
+    #[inline]
+    fn index(self) -> usize {
+        self.get() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::size_of;
+
+    use super::{IndexType, NonMaxU32};
+
+    #[test]
+    fn test_index_types() {
+        assert_eq!(size_of::<Option<NonMaxU32>>(), size_of::<u32>());
+        assert_eq!(NonMaxU32::new(u32::MAX), None);
+        assert_eq!(<NonMaxU32 as IndexType>::new(7).index(), 7);
+        assert!(<NonMaxU32 as IndexType>::new(1) < <NonMaxU32 as IndexType>::new(2));
+        assert_eq!(<u16 as IndexType>::try_new(70_000), None);
+        assert_eq!(<u32 as IndexType>::try_new(70_000), Some(70_000));
+        assert_eq!(<usize as IndexType>::new(5).index(), 5);
+    }
+}
@@ -0,0 +1,431 @@
+//! A graph keyed by arbitrary hashable node identifiers.
+use core::{hash::Hash, marker::PhantomData};
+use std::collections::HashMap;
+
+use super::{
+    adjlist,
+    index::IndexType,
+    traits::{Children, Directed, Direction, Incoming, NodeCount, Outgoing, Parents},
+};
+
+/// A graph whose nodes are identified by keys of type `K` instead of indices.
+///
+/// Each key is assigned a dense index when it is first added, in insertion
+/// order, so the graph can be passed to any algorithm working on indices.
+/// Use [`GraphMap::key`] and [`GraphMap::keys_of`] to translate the results
+/// back to keys.
+///
+/// There is at most one edge between two nodes; adding it again replaces its weight.
+///
+/// Node and edge indices are stored as `Ix`, see [`IndexType`].
+#[derive(Debug, Clone)]
+pub struct GraphMap<K, E, Ty = Directed, Ix = usize> {
+    /// Maps a node index to its key.
+    nodes: Vec<K>,
+    /// Maps a key to its node index.
+    indices: HashMap<K, Ix>,
+    /// The edges between node indices.
+    graph: adjlist::Graph<E, Ix>,
+    /// Whether the graph is directed or undirected.
+    ty: PhantomData<Ty>,
+}
+
+impl<K, E, Ty, Ix: IndexType> Default for GraphMap<K, E, Ty, Ix> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+            graph: adjlist::Graph::default(),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<K, E, Ty> GraphMap<K, E, Ty>
+where
+    K: Hash + Eq + Clone,
+    Ty: Direction,
+{
+    /// Constructs an empty graph.
+    ///
+    /// Use [`GraphMap::default`] to construct a graph with a different index type.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K, E, Ty, Ix> GraphMap<K, E, Ty, Ix>
+where
+    K: Hash + Eq + Clone,
+    Ty: Direction,
+    Ix: IndexType,
+{
+    /// Returns the number of nodes in the graph.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the graph contains no nodes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the number of edges in the graph.
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    /// Adds a node to the graph, returning its index.
+    /// Returns the existing index if the node is already in the graph.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node index doesn't fit in the index type.
+    pub fn add_node(&mut self, key: K) -> usize {
+        if let Some(&index) = self.indices.get(&key) {
+            return index.index();
+        }
+        let index = self.nodes.len();
+        self.indices.insert(key.clone(), Ix::new(index));
+        self.nodes.push(key);
+        index
+    }
+
+    /// Adds an edge from `a` to `b` to the graph, adding the nodes if needed.
+    ///
+    /// If the edge already exists its weight is replaced and the previous
+    /// weight is returned.
+    pub fn add_edge(&mut self, a: K, b: K, weight: E) -> Option<E> {
+        let a = self.add_node(a);
+        let b = self.add_node(b);
+        match self.find_edge(a, b) {
+            Some(edge) => self
+                .graph
+                .edge_weight_mut(edge)
+                .map(|old| core::mem::replace(old, weight)),
+            None => {
+                self.graph.add_edge(a, b, weight);
+                None
+            }
+        }
+    }
+
+    /// Returns the index of a node, or `None` if the node doesn't exist.
+    #[must_use]
+    pub fn index(&self, key: &K) -> Option<usize> {
+        self.indices.get(key).map(|&index| index.index())
+    }
+
+    /// Returns the key of a node index, or `None` if the node doesn't exist.
+    #[must_use]
+    pub fn key(&self, node: usize) -> Option<&K> {
+        self.nodes.get(node)
+    }
+
+    /// Returns the keys of all nodes, ordered by node index.
+    #[must_use]
+    pub fn keys(&self) -> &[K] {
+        &self.nodes
+    }
+
+    /// Translates node indices, e.g. the output of a traversal, to keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the nodes don't exist.
+    pub fn keys_of<I>(&self, nodes: I) -> Vec<&K>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        nodes.into_iter().map(|node| &self.nodes[node]).collect()
+    }
+
+    /// Returns `true` if the node is in the graph.
+    #[must_use]
+    pub fn contains_node(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// Returns `true` if there is an edge from `a` to `b`.
+    #[must_use]
+    pub fn contains_edge(&self, a: &K, b: &K) -> bool {
+        self.edge_weight(a, b).is_some()
+    }
+
+    /// Returns the weight of the edge from `a` to `b`, or `None` if the edge
+    /// doesn't exist.
+    #[must_use]
+    pub fn edge_weight(&self, a: &K, b: &K) -> Option<&E> {
+        let edge = self.find_edge(self.index(a)?, self.index(b)?)?;
+        self.graph.edge_weight(edge)
+    }
+
+    /// Returns the edge between two node indices in the underlying adjacency list.
+    fn find_edge(&self, a: usize, b: usize) -> Option<usize> {
+        let find = |from: usize, to: usize| {
+            self.graph
+                .neighbors(from)
+                .find(|&(node, _)| node == to)
+                .map(|(_, edge)| edge)
+        };
+        if Ty::is_directed() {
+            find(a, b)
+        } else {
+            find(a, b).or_else(|| find(b, a))
+        }
+    }
+}
+
+impl<const N: usize, K, E, Ty> From<[(K, K, E); N]> for GraphMap<K, E, Ty>
+where
+    K: Hash + Eq + Clone,
+    Ty: Direction,
+{
+    /// Constructs a weighted graph from an array of edges.
+    fn from(edges: [(K, K, E); N]) -> Self {
+        edges.into_iter().collect()
+    }
+}
+
+impl<const N: usize, K, Ty> From<[(K, K); N]> for GraphMap<K, (), Ty>
+where
+    K: Hash + Eq + Clone,
+    Ty: Direction,
+{
+    /// Constructs an unweighted graph from an array of edges.
+    fn from(edges: [(K, K); N]) -> Self {
+        edges.into_iter().collect()
+    }
+}
+
+impl<K, E, Ty, Ix> FromIterator<(K, K, E)> for GraphMap<K, E, Ty, Ix>
+where
+    K: Hash + Eq + Clone,
+    Ty: Direction,
+    Ix: IndexType,
+{
+    fn from_iter<I: IntoIterator<Item = (K, K, E)>>(iter: I) -> Self {
+        let mut graph = Self::default();
+        for (from, to, weight) in iter {
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+}
+
+impl<K, Ty, Ix> FromIterator<(K, K)> for GraphMap<K, (), Ty, Ix>
+where
+    K: Hash + Eq + Clone,
+    Ty: Direction,
+    Ix: IndexType,
+{
+    fn from_iter<I: IntoIterator<Item = (K, K)>>(iter: I) -> Self {
+        iter.into_iter().map(|(from, to)| (from, to, ())).collect()
+    }
+}
+
+/// Iterator over the neighbors of a node.
+///
+/// For undirected graphs, yields the nodes at the other end of both the edges
+/// stored as outgoing and the ones stored as incoming.
+#[derive(Debug, Clone)]
+pub struct Neighbors<I> {
+    /// The edges stored in the requested direction.
+    first: I,
+    /// The edges stored in the opposite direction, for undirected graphs.
+    second: Option<I>,
+    /// The node whose neighbors are iterated, to skip self-loops in `second`.
+    node: usize,
+}
+
+/// The node at the far end of an adjacency item.
+trait Endpoint {
+    /// Returns the node at the far end.
+    fn endpoint(&self) -> usize;
+}
+
+impl Endpoint for usize {
+    fn endpoint(&self) -> usize {
+        *self
+    }
+}
+
+impl<E> Endpoint for (usize, E) {
+    fn endpoint(&self) -> usize {
+        self.0
+    }
+}
+
+impl<I> Iterator for Neighbors<I>
+where
+    I: Iterator,
+    I::Item: Endpoint,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if let Some(item) = self.first.next() {
+            return Some(item);
+        }
+        let node = self.node;
+        self.second.as_mut()?.find(|item| item.endpoint() != node)
+    }
+}
+
+impl<K, E, Ty, Ix> GraphMap<K, E, Ty, Ix>
+where
+    Ty: Direction,
+{
+    /// Combines the adjacency in the requested and the opposite direction.
+    fn combine<I>(node: usize, first: I, second: I) -> Neighbors<I> {
+        Neighbors {
+            first,
+            second: (!Ty::is_directed()).then_some(second),
+            node,
+        }
+    }
+}
+
+impl<'graph, K, E, Ty, Ix> Children for &'graph GraphMap<K, E, Ty, Ix>
+where
+    Ty: Direction,
+    Ix: IndexType,
+{
+    type Iter = Neighbors<adjlist::Neighbors<'graph, E, Ix>>;
+
+    fn children(self, node: usize) -> Self::Iter {
+        GraphMap::<K, E, Ty, Ix>::combine(node, self.graph.children(node), self.graph.parents(node))
+    }
+}
+
+impl<'graph, K, E, Ty, Ix> Parents for &'graph GraphMap<K, E, Ty, Ix>
+where
+    Ty: Direction,
+    Ix: IndexType,
+{
+    type Iter = Neighbors<adjlist::Neighbors<'graph, E, Ix>>;
+
+    fn parents(self, node: usize) -> Self::Iter {
+        GraphMap::<K, E, Ty, Ix>::combine(node, self.graph.parents(node), self.graph.children(node))
+    }
+}
+
+impl<'graph, K, E, Ty, Ix> Outgoing<&'graph E> for &'graph GraphMap<K, E, Ty, Ix>
+where
+    Ty: Direction,
+    Ix: IndexType,
+{
+    type Iter = Neighbors<adjlist::Edges<'graph, E, Ix>>;
+
+    fn outgoing(self, node: usize) -> Self::Iter {
+        GraphMap::<K, E, Ty, Ix>::combine(
+            node,
+            self.graph.outgoing(node),
+            self.graph.incoming(node),
+        )
+    }
+}
+
+impl<'graph, K, E, Ty, Ix> Incoming<&'graph E> for &'graph GraphMap<K, E, Ty, Ix>
+where
+    Ty: Direction,
+    Ix: IndexType,
+{
+    type Iter = Neighbors<adjlist::Edges<'graph, E, Ix>>;
+
+    fn incoming(self, node: usize) -> Self::Iter {
+        GraphMap::<K, E, Ty, Ix>::combine(
+            node,
+            self.graph.incoming(node),
+            self.graph.outgoing(node),
+        )
+    }
+}
+
+impl<K, E, Ty, Ix> NodeCount for &GraphMap<K, E, Ty, Ix> {
+    fn node_count(self) -> usize {
+        self.nodes.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GraphMap;
+    use crate::{
+        dominance::immediate_dominators,
+        graph::{
+            index::NonMaxU32,
+            traits::{Children, Directed, Outgoing, Parents, Undirected},
+        },
+        traversal::topological::Topological,
+    };
+
+    #[test]
+    fn test_graph_map() {
+        let mut graph = GraphMap::<_, _, Directed>::new();
+        assert_eq!(graph.add_edge("a", "b", 1), None);
+        assert_eq!(graph.add_edge("b", "c", 2), None);
+        assert_eq!(graph.add_edge("a", "b", 3), Some(1));
+        assert_eq!(graph.add_node("d"), 3);
+        assert_eq!(graph.add_node("a"), 0);
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.edge_weight(&"a", &"b"), Some(&3));
+        assert!(!graph.contains_edge(&"b", &"a"));
+        assert_eq!(graph.index(&"c"), Some(2));
+        assert_eq!(graph.key(2), Some(&"c"));
+        assert_eq!(graph.keys(), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_undirected() {
+        let graph =
+            GraphMap::<_, _, Undirected>::from([("a", "b", 1), ("c", "a", 2), ("a", "a", 3)]);
+        assert!(graph.contains_edge(&"b", &"a"));
+        let Some(a) = graph.index(&"a") else {
+            panic!("node a is missing");
+        };
+        assert_eq!(graph.keys_of(graph.children(a)), [&"a", &"b", &"c"]);
+        assert_eq!(
+            graph.outgoing(a).map(|(_, &w)| w).collect::<Vec<_>>(),
+            [3, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_index_type() {
+        let graph = [("a", "b"), ("b", "c"), ("a", "c")]
+            .into_iter()
+            .collect::<GraphMap<_, _, Directed, NonMaxU32>>();
+        let Some(c) = graph.index(&"c") else {
+            panic!("node c is missing");
+        };
+        assert_eq!(c, 2);
+        assert_eq!(graph.keys_of(graph.parents(c)), [&"a", &"b"]);
+    }
+
+    #[test]
+    fn test_algorithms() {
+        let graph = GraphMap::<_, _, Directed>::from([
+            ("core", "alloc"),
+            ("alloc", "std"),
+            ("core", "std"),
+            ("std", "app"),
+        ]);
+        let order = graph.keys_of(Topological::new(&graph));
+        assert_eq!(order, [&"core", &"alloc", &"std", &"app"]);
+
+        let idoms = immediate_dominators(&graph, 0)
+            .into_iter()
+            .map(|idom| idom.and_then(|node| graph.key(node)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            idoms,
+            [Some(&"core"), Some(&"core"), Some(&"core"), Some(&"std")]
+        );
+    }
+}
@@ -1,15 +1,20 @@
 //! Adjacency matrix graph implementation.
-use core::marker::PhantomData;
+use core::{cmp::Ordering, marker::PhantomData};
 
 use super::{
-    traits::{Children, Directed, Direction, NodeCount, Outgoing, Parents},
-    util::{extend_linearized_matrix, to_linear_matrix_position},
+    traits::{Children, Directed, Direction, Incoming, NodeCount, NodeWeights, Outgoing, Parents},
+    util::{checked_linear_matrix_len, extend_linearized_matrix, to_linear_matrix_position},
 };
+use crate::Error;
 
 /// A graph represented using an adjacency matrix.
-pub struct Graph<E, Ty = Directed> {
+///
+/// Each node can carry a weight of type `N`, which defaults to `()`.
+pub struct Graph<E, Ty = Directed, N = ()> {
     /// The node adjacencies.
     adjacencies: Vec<Option<E>>,
+    /// The node weights.
+    nodes: Vec<N>,
     /// The number of nodes that can be stored in the graph without reallocating.
     n_nodes: usize,
     /// The number of edges in the graph.
@@ -18,30 +23,72 @@ pub struct Graph<E, Ty = Directed> {
     ty: PhantomData<Ty>,
 }
 
-impl<E, Ty> Graph<E, Ty>
+impl<E, Ty: Direction, N> Default for Graph<E, Ty, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E, Ty, N> Graph<E, Ty, N>
 where
     Ty: Direction,
+    N: Default,
 {
-    /// Constructs a new graph.
+    /// Creates a new graph with the specified capacity.
+    /// The nodes are given default weights.
     #[must_use]
-    pub const fn new() -> Self {
-        Self {
-            adjacencies: Vec::new(),
-            n_edges: 0,
-            n_nodes: 0,
-            ty: PhantomData,
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut graph = Self::new();
+        graph.extend_nodes(capacity);
+        graph.nodes.resize_with(capacity, N::default);
+        graph
+    }
+
+    /// Adds an edge from `a` to `b` to the graph.
+    /// Nodes that don't exist yet are added to the graph with default weights.
+    ///
+    /// If the edge already exists its weight is replaced and the previous
+    /// weight is returned.
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: E) -> Option<E> {
+        // allocate if needed
+        let max_node = (a + 1).max(b + 1);
+        if max_node > self.n_nodes {
+            self.extend_nodes(max_node);
+            self.nodes.resize_with(max_node, N::default);
         }
+        self.update_edge(a, b, weight)
     }
 
-    /// Creates a new graph with the specified capacity.
+    /// Adds an edge from `a` to `b` to the graph, like [`Graph::add_edge`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::CapacityOverflow`] if the matrix can't grow to hold
+    /// the nodes.
+    pub fn try_add_edge(&mut self, a: usize, b: usize, weight: E) -> Result<Option<E>, Error> {
+        let max_node = a.max(b).checked_add(1).ok_or(Error::CapacityOverflow)?;
+        if max_node > self.n_nodes {
+            let len = checked_linear_matrix_len::<Ty>(max_node).ok_or(Error::CapacityOverflow)?;
+            self.adjacencies
+                .try_reserve(len.saturating_sub(self.adjacencies.len()))
+                .map_err(|_| Error::CapacityOverflow)?;
+        }
+        Ok(self.add_edge(a, b, weight))
+    }
+}
+
+impl<E, Ty, N> Graph<E, Ty, N>
+where
+    Ty: Direction,
+{
+    /// Constructs a new graph.
     #[must_use]
-    pub fn with_capacity(capacity: usize) -> Self {
-        let mut adjacencies = Vec::new();
-        extend_linearized_matrix::<Ty, Option<E>>(&mut adjacencies, 0, capacity);
+    pub const fn new() -> Self {
         Self {
-            adjacencies,
+            adjacencies: Vec::new(),
+            nodes: Vec::new(),
             n_edges: 0,
-            n_nodes: capacity,
+            n_nodes: 0,
             ty: PhantomData,
         }
     }
@@ -52,25 +99,180 @@ where
         self.n_edges == 0
     }
 
-    /// Adds an edge from `a` to `b` to the graph.
+    /// Returns the number of edges in the graph.
+    #[must_use]
+    pub const fn edge_count(&self) -> usize {
+        self.n_edges
+    }
+
+    /// Adds a node with the given weight to the graph, returning its index.
+    pub fn add_node(&mut self, weight: N) -> usize {
+        let node = self.n_nodes;
+        self.extend_nodes(node + 1);
+        self.nodes.push(weight);
+        node
+    }
+
+    /// Returns the weight of a node, or `None` if the node doesn't exist.
+    #[must_use]
+    pub fn node_weight(&self, node: usize) -> Option<&N> {
+        self.nodes.get(node)
+    }
+
+    /// Returns a mutable reference to the weight of a node, or `None` if the
+    /// node doesn't exist.
+    pub fn node_weight_mut(&mut self, node: usize) -> Option<&mut N> {
+        self.nodes.get_mut(node)
+    }
+
+    /// Grows the adjacency matrix to hold `n_nodes` nodes.
+    fn extend_nodes(&mut self, n_nodes: usize) {
+        extend_linearized_matrix::<Ty, Option<E>>(&mut self.adjacencies, self.n_nodes, n_nodes);
+        self.n_nodes = n_nodes;
+    }
+
+    /// Adds or replaces the edge from `a` to `b`, returning the previous weight.
     ///
     /// # Panics
     ///
     /// Panics if either of the nodes don't exist.
-    pub fn add_edge(&mut self, a: usize, b: usize, weight: E) {
-        // allocate if needed
-        let max_node = (a + 1).max(b + 1);
-        if max_node > self.n_nodes {
-            extend_linearized_matrix::<Ty, Option<E>>(
-                &mut self.adjacencies,
-                self.n_nodes,
-                max_node,
-            );
-            self.n_nodes = max_node;
+    pub fn update_edge(&mut self, a: usize, b: usize, weight: E) -> Option<E> {
+        assert!(
+            a < self.n_nodes && b < self.n_nodes,
+            "node out of range for graph with {} nodes",
+            self.n_nodes
+        );
+        let index = to_linear_matrix_position::<Ty>(a, b, self.n_nodes);
+        let old = self.adjacencies[index].replace(weight);
+        if old.is_none() {
+            self.n_edges += 1;
         }
+        old
+    }
 
-        let index = to_linear_matrix_position::<Ty>(a, b, self.n_nodes);
-        self.adjacencies[index] = Some(weight);
+    /// Adds or replaces the edge from `a` to `b`, like [`Graph::update_edge`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NodeOutOfRange`] if either of the nodes don't exist.
+    pub fn try_update_edge(&mut self, a: usize, b: usize, weight: E) -> Result<Option<E>, Error> {
+        self.check_node(a)?;
+        self.check_node(b)?;
+        Ok(self.update_edge(a, b, weight))
+    }
+
+    /// Removes the edge from `a` to `b`, returning its weight.
+    /// Returns `None` if the edge doesn't exist.
+    pub fn remove_edge(&mut self, a: usize, b: usize) -> Option<E> {
+        let index = self.edge_position(a, b)?;
+        let old = self.adjacencies[index].take();
+        if old.is_some() {
+            self.n_edges -= 1;
+        }
+        old
+    }
+
+    /// Returns `true` if there is an edge from `a` to `b`.
+    #[must_use]
+    pub fn contains_edge(&self, a: usize, b: usize) -> bool {
+        self.edge_weight(a, b).is_some()
+    }
+
+    /// Returns the weight of the edge from `a` to `b`, or `None` if the edge
+    /// doesn't exist.
+    #[must_use]
+    pub fn edge_weight(&self, a: usize, b: usize) -> Option<&E> {
+        self.adjacencies[self.edge_position(a, b)?].as_ref()
+    }
+
+    /// Returns a mutable reference to the weight of the edge from `a` to `b`,
+    /// or `None` if the edge doesn't exist.
+    pub fn edge_weight_mut(&mut self, a: usize, b: usize) -> Option<&mut E> {
+        let index = self.edge_position(a, b)?;
+        self.adjacencies[index].as_mut()
+    }
+
+    /// Removes a node and all of its edges from the graph.
+    ///
+    /// Nodes with a larger index are shifted down by one to keep the matrix
+    /// compact. Returns a map from old node indices to new node indices, where
+    /// the removed node maps to `None`.
+    ///
+    /// Time complexity: **O(|V|^2)**
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node doesn't exist.
+    pub fn remove_node(&mut self, node: usize) -> Vec<Option<usize>> {
+        assert!(
+            node < self.n_nodes,
+            "node out of range for graph with {} nodes",
+            self.n_nodes
+        );
+        let n_nodes = self.n_nodes - 1;
+        // maps a new node index to the old one
+        let old_index = |i: usize| if i < node { i } else { i + 1 };
+
+        let mut adjacencies = Vec::new();
+        extend_linearized_matrix::<Ty, Option<E>>(&mut adjacencies, 0, n_nodes);
+        for row in 0..n_nodes {
+            for column in 0..n_nodes {
+                if !Ty::is_directed() && column > row {
+                    break;
+                }
+                let old = to_linear_matrix_position::<Ty>(
+                    old_index(row),
+                    old_index(column),
+                    self.n_nodes,
+                );
+                let new = to_linear_matrix_position::<Ty>(row, column, n_nodes);
+                adjacencies[new] = self.adjacencies[old].take();
+            }
+        }
+
+        // everything left behind was incident to the removed node
+        self.n_edges -= self.adjacencies.iter().filter(|e| e.is_some()).count();
+        self.adjacencies = adjacencies;
+        self.nodes.remove(node);
+        self.n_nodes = n_nodes;
+
+        (0..=n_nodes)
+            .map(|i| match i.cmp(&node) {
+                Ordering::Less => Some(i),
+                Ordering::Equal => None,
+                Ordering::Greater => Some(i - 1),
+            })
+            .collect()
+    }
+
+    /// Removes a node and all of its edges from the graph, like
+    /// [`Graph::remove_node`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NodeOutOfRange`] if the node doesn't exist.
+    pub fn try_remove_node(&mut self, node: usize) -> Result<Vec<Option<usize>>, Error> {
+        self.check_node(node)?;
+        Ok(self.remove_node(node))
+    }
+
+    /// Returns an error if the node doesn't exist.
+    const fn check_node(&self, node: usize) -> Result<(), Error> {
+        if node < self.n_nodes {
+            Ok(())
+        } else {
+            Err(Error::NodeOutOfRange {
+                node,
+                node_count: self.n_nodes,
+            })
+        }
+    }
+
+    /// Returns the position of the edge from `a` to `b` in the adjacency matrix,
+    /// or `None` if either of the nodes don't exist.
+    fn edge_position(&self, a: usize, b: usize) -> Option<usize> {
+        (a < self.n_nodes && b < self.n_nodes)
+            .then(|| to_linear_matrix_position::<Ty>(a, b, self.n_nodes))
     }
 }
 
@@ -110,9 +312,9 @@ impl<Ty: Direction> FromIterator<(usize, usize)> for Graph<(), Ty> {
 /// The direction of the iterator.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IterDirection {
-    /// Iterate over rows i.e. children.
+    /// Iterate over the rows of a column i.e. parents or incoming edges.
     Rows,
-    /// Iterate over columns i.e. parents.
+    /// Iterate over the columns of a row i.e. children or outgoing edges.
     Columns,
 }
 
@@ -206,7 +408,7 @@ impl<'graph, Ty: Direction, E: 'graph> Iterator for Neighbors<'graph, Ty, E> {
     }
 }
 
-impl<'graph, E, Ty> Children for &'graph Graph<E, Ty>
+impl<'graph, E, Ty, N> Children for &'graph Graph<E, Ty, N>
 where
     Ty: Direction,
 {
@@ -224,7 +426,7 @@ where
     }
 }
 
-impl<'graph, E, Ty> Parents for &'graph Graph<E, Ty>
+impl<'graph, E, Ty, N> Parents for &'graph Graph<E, Ty, N>
 where
     Ty: Direction,
 {
@@ -242,13 +444,13 @@ where
     }
 }
 
-impl<'graph, E, Ty> NodeCount for &'graph Graph<E, Ty> {
+impl<E, Ty, N> NodeCount for &Graph<E, Ty, N> {
     fn node_count(self) -> usize {
         self.n_nodes
     }
 }
 
-impl<'graph, E, Ty> Outgoing<&'graph E> for &'graph Graph<E, Ty>
+impl<'graph, E, Ty, N> Outgoing<&'graph E> for &'graph Graph<E, Ty, N>
 where
     Ty: Direction,
 {
@@ -266,12 +468,43 @@ where
     }
 }
 
+impl<'graph, E, Ty, N> Incoming<&'graph E> for &'graph Graph<E, Ty, N>
+where
+    Ty: Direction,
+{
+    type Iter = Edges<'graph, Ty, E>;
+
+    fn incoming(self, node: usize) -> Edges<'graph, Ty, E> {
+        Edges {
+            iter_direction: IterDirection::Rows,
+            adjacencies: &self.adjacencies,
+            node_capacity: self.n_nodes,
+            row: 0,
+            column: node,
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<'graph, E, Ty, N> NodeWeights<&'graph N> for &'graph Graph<E, Ty, N>
+where
+    Ty: Direction,
+{
+    fn node_weight(self, node: usize) -> Option<&'graph N> {
+        self.nodes.get(node)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::graph::traits::Directed;
+    use crate::graph::traits::Incoming;
     use crate::graph::traits::NodeCount;
+    use crate::graph::traits::Outgoing;
+    use crate::graph::traits::Undirected;
 
     use super::Graph;
+    use crate::Error;
 
     #[test]
     fn graph_from_iter() {
@@ -279,4 +512,110 @@ mod tests {
         let graph: Graph<(), Directed> = Graph::from_iter(edges);
         assert_eq!(graph.node_count(), 6);
     }
+
+    #[test]
+    fn graph_incoming() {
+        let graph = Graph::<_, Directed>::from([(0, 2, 'a'), (1, 2, 'b'), (2, 0, 'c')]);
+        assert_eq!(
+            graph.incoming(2).collect::<Vec<_>>(),
+            [(0, &'a'), (1, &'b')]
+        );
+        assert_eq!(graph.incoming(0).collect::<Vec<_>>(), [(2, &'c')]);
+        assert_eq!(graph.incoming(1).count(), 0);
+
+        let graph = Graph::<_, Undirected>::from([(0, 2, 'a'), (1, 2, 'b')]);
+        assert_eq!(
+            graph.incoming(2).collect::<Vec<_>>(),
+            graph.outgoing(2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn graph_mutation() {
+        let mut graph = Graph::<_, Directed>::new();
+        assert_eq!(graph.add_edge(0, 1, 'a'), None);
+        assert_eq!(graph.add_edge(1, 2, 'b'), None);
+        assert_eq!(graph.add_edge(2, 0, 'c'), None);
+        assert_eq!(graph.add_edge(0, 1, 'd'), Some('a'));
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.update_edge(2, 1, 'e'), None);
+        assert_eq!(graph.edge_count(), 4);
+
+        assert!(graph.contains_edge(0, 1));
+        assert!(!graph.contains_edge(1, 0));
+        assert!(!graph.contains_edge(0, 7));
+        assert_eq!(graph.edge_weight(0, 1), Some(&'d'));
+        if let Some(weight) = graph.edge_weight_mut(1, 2) {
+            *weight = 'f';
+        }
+        assert_eq!(graph.edge_weight(1, 2), Some(&'f'));
+
+        assert_eq!(graph.remove_edge(0, 1), Some('d'));
+        assert_eq!(graph.remove_edge(0, 1), None);
+        assert_eq!(graph.remove_edge(5, 1), None);
+        assert_eq!(graph.edge_count(), 3);
+
+        // edges 1->2, 2->0, 2->1
+        assert_eq!(graph.remove_node(1), [Some(0), None, Some(1)]);
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(graph.outgoing(1).collect::<Vec<_>>(), [(0, &'c')]);
+
+        let mut graph = Graph::<_, Undirected>::from([(0, 1), (1, 2), (2, 3), (3, 3)]);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.remove_node(1), [Some(0), None, Some(1), Some(2)]);
+        assert_eq!(graph.edge_count(), 2);
+        assert!(graph.contains_edge(2, 1));
+        assert!(graph.contains_edge(2, 2));
+        assert!(!graph.contains_edge(0, 1));
+        assert!(graph.remove_edge(1, 2).is_some());
+        assert!(graph.remove_edge(2, 2).is_some());
+        assert!(graph.is_empty());
+    }
+
+    #[test]
+    fn graph_errors() {
+        let mut graph = Graph::<_, Directed>::from([(0, 1)]);
+        assert_eq!(
+            graph.try_update_edge(0, 2, ()),
+            Err(Error::NodeOutOfRange {
+                node: 2,
+                node_count: 2
+            })
+        );
+        assert!(graph.try_remove_node(2).is_err());
+        assert_eq!(
+            graph.try_add_edge(usize::MAX, 0, ()),
+            Err(Error::CapacityOverflow)
+        );
+        assert_eq!(
+            graph.try_add_edge(1 << 40, 0, ()),
+            Err(Error::CapacityOverflow)
+        );
+        assert_eq!(graph.try_add_edge(1, 2, ()), Ok(None));
+        assert_eq!(graph.try_remove_node(0), Ok(vec![None, Some(0), Some(1)]));
+    }
+
+    #[test]
+    fn graph_node_weights() {
+        let mut graph = Graph::<_, Directed, _>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.update_edge(a, b, 1);
+        graph.update_edge(b, c, 2);
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.node_weight(b), Some(&"b"));
+        if let Some(weight) = graph.node_weight_mut(c) {
+            *weight = "d";
+        }
+        assert_eq!(graph.remove_node(a), [None, Some(0), Some(1)]);
+        assert_eq!(graph.node_weight(1), Some(&"d"));
+        assert_eq!(graph.node_weight(2), None);
+        assert_eq!(graph.edge_weight(0, 1), Some(&2));
+
+        let mut graph = Graph::<(), Directed, u32>::new();
+        graph.add_edge(0, 2, ());
+        assert_eq!(graph.node_weight(1), Some(&0));
+    }
 }
@@ -1,6 +1,12 @@
 //! A graph is a collection of nodes and edges.
 //! <https://en.wikipedia.org/wiki/Graph_(discrete_mathematics)>
+pub mod adjlist;
+pub mod bitmatrix;
+pub mod csr;
+pub mod index;
+pub mod map;
 pub mod matrix;
+pub mod stable;
 pub mod traits;
 pub mod util;
 pub mod visit_map;
@@ -0,0 +1,692 @@
+//! A graph with node and edge indices that stay valid across removals.
+
+use super::{
+    index::IndexType,
+    traits::{Children, Incoming, NodeCount, NodeWeights, Outgoing, Parents},
+};
+use crate::Error;
+
+/// Index of the outgoing adjacency lists.
+const OUTGOING: usize = 0;
+/// Index of the incoming adjacency lists.
+const INCOMING: usize = 1;
+
+/// A slot in the node or edge storage of a [`StableGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Entry<T, Ix> {
+    /// A slot holding a node or edge.
+    Occupied(T),
+    /// A vacant slot, linking to the next vacant slot in the free list.
+    Vacant(Option<Ix>),
+}
+
+impl<T, Ix> Entry<T, Ix> {
+    /// Returns the contents of an occupied slot.
+    const fn get(&self) -> Option<&T> {
+        match self {
+            Self::Occupied(value) => Some(value),
+            Self::Vacant(_) => None,
+        }
+    }
+
+    /// Returns the contents of an occupied slot mutably.
+    fn get_mut(&mut self) -> Option<&mut T> {
+        match self {
+            Self::Occupied(value) => Some(value),
+            Self::Vacant(_) => None,
+        }
+    }
+}
+
+/// A node of a [`StableGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Node<N, Ix> {
+    /// The node payload.
+    weight: N,
+    /// The first edge in the node's outgoing and incoming adjacency lists.
+    first: [Option<Ix>; 2],
+}
+
+/// An edge of a [`StableGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Edge<E, Ix> {
+    /// The edge payload.
+    weight: E,
+    /// The nodes that the edge starts from and points to.
+    endpoints: [Ix; 2],
+    /// The next edge in the same outgoing and incoming adjacency lists.
+    next: [Option<Ix>; 2],
+}
+
+/// A directed graph whose node and edge indices stay valid across removals.
+///
+/// Removed nodes and edges leave vacant slots behind, which are reused by
+/// later insertions through a free list. Vacant node slots have no edges, so
+/// algorithms see them as isolated nodes.
+///
+/// Allows parallel edges and self-loops.
+///
+/// Node and edge indices are stored as `Ix`, see [`IndexType`].
+///
+/// Space complexity: **O(|V| + |E|)**
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StableGraph<N, E, Ix = usize> {
+    /// The node slots.
+    nodes: Vec<Entry<Node<N, Ix>, Ix>>,
+    /// The edge slots.
+    edges: Vec<Entry<Edge<E, Ix>, Ix>>,
+    /// The first vacant node slot.
+    free_node: Option<Ix>,
+    /// The first vacant edge slot.
+    free_edge: Option<Ix>,
+    /// The number of occupied node slots.
+    n_nodes: usize,
+    /// The number of occupied edge slots.
+    n_edges: usize,
+}
+
+impl<N, E, Ix: IndexType> Default for StableGraph<N, E, Ix> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            free_node: None,
+            free_edge: None,
+            n_nodes: 0,
+            n_edges: 0,
+        }
+    }
+}
+
+impl<N, E> StableGraph<N, E> {
+    /// Constructs an empty graph.
+    ///
+    /// Use [`StableGraph::default`] to construct a graph with a different index type.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            free_node: None,
+            free_edge: None,
+            n_nodes: 0,
+            n_edges: 0,
+        }
+    }
+
+    /// Constructs an empty graph with hints for number of nodes and edges
+    /// to reduce unnecessary allocations.
+    #[must_use]
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(nodes),
+            edges: Vec::with_capacity(edges),
+            ..Self::new()
+        }
+    }
+}
+
+impl<N, E, Ix: IndexType> StableGraph<N, E, Ix> {
+    /// Returns the number of nodes in the graph, not counting vacant slots.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.n_nodes
+    }
+
+    /// Returns an upper bound on the node indices in the graph, which is what
+    /// [`NodeCount::node_count`] returns for the graph.
+    #[must_use]
+    pub fn node_bound(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of edges in the graph.
+    #[must_use]
+    pub const fn edge_count(&self) -> usize {
+        self.n_edges
+    }
+
+    /// Returns `true` if the graph contains no edges.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.n_edges == 0
+    }
+
+    /// Returns `true` if the node exists.
+    #[must_use]
+    pub fn contains_node(&self, node: usize) -> bool {
+        self.node(node).is_some()
+    }
+
+    /// Returns `true` if the edge exists.
+    #[must_use]
+    pub fn contains_edge(&self, edge: usize) -> bool {
+        self.edge(edge).is_some()
+    }
+
+    /// Returns the weight of a node, or `None` if the node doesn't exist.
+    #[must_use]
+    pub fn node_weight(&self, node: usize) -> Option<&N> {
+        self.node(node).map(|node| &node.weight)
+    }
+
+    /// Returns a mutable reference to the weight of a node, or `None` if the
+    /// node doesn't exist.
+    pub fn node_weight_mut(&mut self, node: usize) -> Option<&mut N> {
+        self.nodes
+            .get_mut(node)
+            .and_then(Entry::get_mut)
+            .map(|node| &mut node.weight)
+    }
+
+    /// Returns the weight of an edge, or `None` if the edge doesn't exist.
+    #[must_use]
+    pub fn edge_weight(&self, edge: usize) -> Option<&E> {
+        self.edge(edge).map(|edge| &edge.weight)
+    }
+
+    /// Returns a mutable reference to the weight of an edge, or `None` if the
+    /// edge doesn't exist.
+    pub fn edge_weight_mut(&mut self, edge: usize) -> Option<&mut E> {
+        self.edges
+            .get_mut(edge)
+            .and_then(Entry::get_mut)
+            .map(|edge| &mut edge.weight)
+    }
+
+    /// Returns the `(from, to)` nodes of an edge, or `None` if the edge doesn't exist.
+    #[must_use]
+    pub fn edge_endpoints(&self, edge: usize) -> Option<(usize, usize)> {
+        self.edge(edge)
+            .map(|edge| (edge.endpoints[0].index(), edge.endpoints[1].index()))
+    }
+
+    /// Returns the index of an edge from `a` to `b`, or `None` if there is none.
+    #[must_use]
+    pub fn find_edge(&self, a: usize, b: usize) -> Option<usize> {
+        self.neighbors(a)
+            .find(|&(node, _)| node == b)
+            .map(|(_, edge)| edge)
+    }
+
+    /// Returns an iterator over the indices of the nodes in the graph.
+    pub fn node_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.get().is_some())
+            .map(|(i, _)| i)
+    }
+
+    /// Adds a node to the graph, returning its index.
+    /// Reuses the most recently vacated slot if there is one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node index doesn't fit in the index type.
+    pub fn add_node(&mut self, weight: N) -> usize {
+        let node = Node {
+            weight,
+            first: [None, None],
+        };
+        let index = if let Some(index) = self.free_node {
+            let index = index.index();
+            if let Entry::Vacant(next) = self.nodes[index] {
+                self.free_node = next;
+            }
+            self.nodes[index] = Entry::Occupied(node);
+            index
+        } else {
+            let index = self.nodes.len();
+            assert!(index <= Ix::MAX, "index out of range for index type");
+            self.nodes.push(Entry::Occupied(node));
+            index
+        };
+        self.n_nodes += 1;
+        index
+    }
+
+    /// Adds an edge from `a` to `b` to the graph, returning its index.
+    /// Reuses the most recently vacated slot if there is one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either of the nodes don't exist, or if the edge index doesn't
+    /// fit in the index type.
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: E) -> usize {
+        assert!(
+            self.contains_node(a) && self.contains_node(b),
+            "node doesn't exist"
+        );
+        let index = match self.free_edge {
+            Some(index) => {
+                let index = index.index();
+                if let Entry::Vacant(next) = self.edges[index] {
+                    self.free_edge = next;
+                }
+                index
+            }
+            None => {
+                let index = self.edges.len();
+                assert!(index <= Ix::MAX, "index out of range for index type");
+                self.edges.push(Entry::Vacant(None));
+                index
+            }
+        };
+
+        // add the edge to the front of both adjacency lists
+        let next = [self.first_link(a, OUTGOING), self.first_link(b, INCOMING)];
+        self.set_first(a, OUTGOING, Some(index));
+        self.set_first(b, INCOMING, Some(index));
+        self.edges[index] = Entry::Occupied(Edge {
+            weight,
+            endpoints: [Ix::new(a), Ix::new(b)],
+            next,
+        });
+        self.n_edges += 1;
+        index
+    }
+
+    /// Adds a node to the graph, like [`StableGraph::add_node`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::CapacityOverflow`] if the node index doesn't fit in
+    /// the index type.
+    pub fn try_add_node(&mut self, weight: N) -> Result<usize, Error> {
+        if self.free_node.is_none() && Ix::try_new(self.nodes.len()).is_none() {
+            return Err(Error::CapacityOverflow);
+        }
+        Ok(self.add_node(weight))
+    }
+
+    /// Adds an edge from `a` to `b` to the graph, like [`StableGraph::add_edge`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NodeOutOfRange`] if either of the nodes don't exist,
+    /// or [`Error::CapacityOverflow`] if the edge index doesn't fit in the
+    /// index type.
+    pub fn try_add_edge(&mut self, a: usize, b: usize, weight: E) -> Result<usize, Error> {
+        if let Some(node) = [a, b].into_iter().find(|&node| !self.contains_node(node)) {
+            return Err(Error::NodeOutOfRange {
+                node,
+                node_count: self.node_bound(),
+            });
+        }
+        if self.free_edge.is_none() && Ix::try_new(self.edges.len()).is_none() {
+            return Err(Error::CapacityOverflow);
+        }
+        Ok(self.add_edge(a, b, weight))
+    }
+
+    /// Removes an edge from the graph, returning its weight.
+    /// Returns `None` if the edge doesn't exist.
+    ///
+    /// Time complexity: **O(degree)**
+    pub fn remove_edge(&mut self, edge: usize) -> Option<E> {
+        let (endpoints, next) = {
+            let edge = self.edge(edge)?;
+            (edge.endpoints, edge.next)
+        };
+        let target = Some(Ix::new(edge));
+        for direction in [OUTGOING, INCOMING] {
+            // the outgoing list belongs to the source, the incoming list to the target
+            let node = endpoints[direction].index();
+            if self.first_link(node, direction) == target {
+                self.set_first(node, direction, next[direction].map(IndexType::index));
+            } else {
+                let mut current = self.first_link(node, direction);
+                while let Some(c) = current {
+                    let link = &mut self.edge_mut(c.index()).next[direction];
+                    if *link == target {
+                        *link = next[direction];
+                        break;
+                    }
+                    current = *link;
+                }
+            }
+        }
+
+        let entry = core::mem::replace(&mut self.edges[edge], Entry::Vacant(self.free_edge));
+        self.free_edge = target;
+        self.n_edges -= 1;
+        match entry {
+            Entry::Occupied(edge) => Some(edge.weight),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    /// Removes a node and all of its edges from the graph, returning its weight.
+    /// Returns `None` if the node doesn't exist.
+    pub fn remove_node(&mut self, node: usize) -> Option<N> {
+        self.node(node)?;
+        for direction in [OUTGOING, INCOMING] {
+            while let Some(edge) = self.node_first(node, direction) {
+                self.remove_edge(edge);
+            }
+        }
+
+        let entry = core::mem::replace(&mut self.nodes[node], Entry::Vacant(self.free_node));
+        self.free_node = Some(Ix::new(node));
+        self.n_nodes -= 1;
+        match entry {
+            Entry::Occupied(node) => Some(node.weight),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    /// Returns an iterator of all node-edge tuples with an edge starting from `node`.
+    /// Produces an empty iterator if `node` doesn't exist.
+    #[must_use]
+    pub fn neighbors(&self, node: usize) -> NeighborIterator<'_, N, E, Ix> {
+        self.adjacent(node, OUTGOING)
+    }
+
+    /// Returns an iterator of all node-edge tuples with an edge ending at `node`.
+    /// Produces an empty iterator if `node` doesn't exist.
+    #[must_use]
+    pub fn neighbors_incoming(&self, node: usize) -> NeighborIterator<'_, N, E, Ix> {
+        self.adjacent(node, INCOMING)
+    }
+
+    /// Returns an iterator over the adjacency list of `node` in the given direction.
+    fn adjacent(&self, node: usize, direction: usize) -> NeighborIterator<'_, N, E, Ix> {
+        NeighborIterator {
+            graph: self,
+            direction,
+            next_edge: self.first_link(node, direction),
+        }
+    }
+
+    /// Returns a node if its slot is occupied.
+    fn node(&self, node: usize) -> Option<&Node<N, Ix>> {
+        self.nodes.get(node).and_then(Entry::get)
+    }
+
+    /// Returns an edge if its slot is occupied.
+    fn edge(&self, edge: usize) -> Option<&Edge<E, Ix>> {
+        self.edges.get(edge).and_then(Entry::get)
+    }
+
+    /// Returns an edge that is known to exist.
+    #[allow(clippy::expect_used)]
+    fn edge_mut(&mut self, edge: usize) -> &mut Edge<E, Ix> {
+        self.edges[edge].get_mut().expect("edge should exist")
+    }
+
+    /// Returns the first edge of a node's adjacency list.
+    fn node_first(&self, node: usize, direction: usize) -> Option<usize> {
+        self.first_link(node, direction).map(IndexType::index)
+    }
+
+    /// Returns the link to the first edge of a node's adjacency list.
+    fn first_link(&self, node: usize, direction: usize) -> Option<Ix> {
+        self.node(node).and_then(|node| node.first[direction])
+    }
+
+    /// Sets the first edge of an existing node's adjacency list.
+    fn set_first(&mut self, node: usize, direction: usize, edge: Option<usize>) {
+        if let Some(node) = self.nodes[node].get_mut() {
+            node.first[direction] = edge.map(Ix::new);
+        }
+    }
+}
+
+/// An iterator for convenient adjacency list traversal.
+#[derive(Debug)]
+pub struct NeighborIterator<'graph, N, E, Ix = usize> {
+    /// The graph that this iterator is iterating over.
+    graph: &'graph StableGraph<N, E, Ix>,
+    /// Whether the outgoing or incoming adjacency list is followed.
+    direction: usize,
+    /// The next edge in the adjacency list.
+    next_edge: Option<Ix>,
+}
+
+impl<N, E, Ix: Clone> Clone for NeighborIterator<'_, N, E, Ix> {
+    fn clone(&self) -> Self {
+        Self {
+            graph: self.graph,
+            direction: self.direction,
+            next_edge: self.next_edge.clone(),
+        }
+    }
+}
+
+impl<N, E, Ix: IndexType> Iterator for NeighborIterator<'_, N, E, Ix> {
+    type Item = (usize, usize);
+
+    /// Produces an adjacent node and the edge connecting it.
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.next_edge?.index();
+        let edge = self.graph.edge(index)?;
+        self.next_edge = edge.next[self.direction];
+        // the neighbor is at the opposite end of the edge
+        Some((edge.endpoints[1 - self.direction].index(), index))
+    }
+}
+
+/// Iterator over the children or parents of a node.
+#[derive(Debug, Clone)]
+pub struct Neighbors<'graph, N, E, Ix = usize> {
+    /// The underlying adjacency list iterator.
+    iter: NeighborIterator<'graph, N, E, Ix>,
+}
+
+impl<N, E, Ix: IndexType> Iterator for Neighbors<'_, N, E, Ix> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(node, _)| node)
+    }
+}
+
+/// Iterator over the edges from or to a node.
+#[derive(Debug, Clone)]
+pub struct Edges<'graph, N, E, Ix = usize> {
+    /// The underlying adjacency list iterator.
+    iter: NeighborIterator<'graph, N, E, Ix>,
+}
+
+impl<'graph, N, E, Ix: IndexType> Iterator for Edges<'graph, N, E, Ix> {
+    type Item = (usize, &'graph E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let graph = self.iter.graph;
+        let (node, edge) = self.iter.next()?;
+        Some((node, graph.edge_weight(edge)?))
+    }
+}
+
+impl<'graph, N, E, Ix: IndexType> Children for &'graph StableGraph<N, E, Ix> {
+    type Iter = Neighbors<'graph, N, E, Ix>;
+
+    fn children(self, node: usize) -> Neighbors<'graph, N, E, Ix> {
+        Neighbors {
+            iter: self.neighbors(node),
+        }
+    }
+}
+
+impl<'graph, N, E, Ix: IndexType> Parents for &'graph StableGraph<N, E, Ix> {
+    type Iter = Neighbors<'graph, N, E, Ix>;
+
+    fn parents(self, node: usize) -> Neighbors<'graph, N, E, Ix> {
+        Neighbors {
+            iter: self.neighbors_incoming(node),
+        }
+    }
+}
+
+/// Returns the node bound, since algorithms use it to size per-node tables.
+/// Algorithms see every slot up to [`StableGraph::node_bound`] and skip the
+/// vacant ones through [`NodeCount::contains_node`].
+impl<N, E, Ix: IndexType> NodeCount for &StableGraph<N, E, Ix> {
+    fn node_count(self) -> usize {
+        self.node_bound()
+    }
+
+    fn contains_node(self, node: usize) -> bool {
+        StableGraph::contains_node(self, node)
+    }
+}
+
+impl<'graph, N, E, Ix: IndexType> Outgoing<&'graph E> for &'graph StableGraph<N, E, Ix> {
+    type Iter = Edges<'graph, N, E, Ix>;
+
+    fn outgoing(self, node: usize) -> Edges<'graph, N, E, Ix> {
+        Edges {
+            iter: self.neighbors(node),
+        }
+    }
+}
+
+impl<'graph, N, E, Ix: IndexType> Incoming<&'graph E> for &'graph StableGraph<N, E, Ix> {
+    type Iter = Edges<'graph, N, E, Ix>;
+
+    fn incoming(self, node: usize) -> Edges<'graph, N, E, Ix> {
+        Edges {
+            iter: self.neighbors_incoming(node),
+        }
+    }
+}
+
+impl<'graph, N, E, Ix: IndexType> NodeWeights<&'graph N> for &'graph StableGraph<N, E, Ix> {
+    fn node_weight(self, node: usize) -> Option<&'graph N> {
+        self.node(node).map(|node| &node.weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StableGraph;
+    use crate::{
+        components::{condensation, strongly_connected},
+        dominance::{immediate_dominators, try_frontiers, try_immediate_dominators},
+        graph::{
+            index::NonMaxU32,
+            traits::{Children, Incoming, NodeCount, Outgoing, Parents},
+        },
+        traversal::{
+            preorder::PreOrder,
+            topological::{toposort, AllTopological, PriorityTopological, Topological},
+        },
+        Error,
+    };
+
+    #[test]
+    fn test_stable_indices() {
+        let mut graph = StableGraph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let ab = graph.add_edge(a, b, 1);
+        let bc = graph.add_edge(b, c, 2);
+        let ca = graph.add_edge(c, a, 3);
+        let bb = graph.add_edge(b, b, 4);
+        assert_eq!(graph.edge_count(), 4);
+
+        assert_eq!(graph.remove_node(b), Some("b"));
+        assert_eq!(graph.remove_node(b), None);
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.edge_count(), 1);
+        assert!(!graph.contains_edge(ab) && !graph.contains_edge(bc) && !graph.contains_edge(bb));
+        assert_eq!(graph.node_weight(c), Some(&"c"));
+        assert_eq!(graph.edge_weight(ca), Some(&3));
+        assert_eq!(graph.node_indices().collect::<Vec<_>>(), [a, c]);
+
+        // vacant slots are reused
+        let d = graph.add_node("d");
+        assert_eq!(d, b);
+        let cd = graph.add_edge(c, d, 5);
+        assert!(cd == ab || cd == bc || cd == bb);
+        assert_eq!(graph.find_edge(c, d), Some(cd));
+        assert_eq!(graph.edge_endpoints(cd), Some((c, d)));
+        assert_eq!(graph.remove_edge(ca), Some(3));
+        assert_eq!(graph.remove_edge(ca), None);
+        assert_eq!(graph.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_traits() {
+        let mut graph = StableGraph::new();
+        let nodes = (0..5).map(|i| graph.add_node(i)).collect::<Vec<_>>();
+        for (a, b) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (0, 4)] {
+            graph.add_edge(nodes[a], nodes[b], a * 10 + b);
+        }
+        assert_eq!(graph.children(0).collect::<Vec<_>>(), [4, 2, 1]);
+        assert_eq!(graph.parents(3).collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(graph.outgoing(3).collect::<Vec<_>>(), [(4, &34)]);
+        assert_eq!(graph.incoming(4).collect::<Vec<_>>(), [(0, &4), (3, &34)]);
+
+        graph.remove_node(2);
+        assert_eq!(NodeCount::node_count(&graph), 5);
+        assert_eq!(graph.parents(2).count(), 0);
+        assert_eq!(graph.children(0).collect::<Vec<_>>(), [4, 1]);
+        let order = PreOrder::new(&graph, 0).collect::<Vec<_>>();
+        assert_eq!(order, [0, 1, 3, 4]);
+        let idoms = immediate_dominators(&graph, 0);
+        assert_eq!(idoms, [Some(0), Some(0), None, Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn test_vacant_slots() {
+        let mut graph = StableGraph::new();
+        let nodes = (0..4).map(|i| graph.add_node(i)).collect::<Vec<_>>();
+        for (a, b) in [(0, 1), (1, 2), (2, 1), (2, 3)] {
+            graph.add_edge(nodes[a], nodes[b], ());
+        }
+        graph.remove_node(nodes[1]);
+        assert!(!NodeCount::contains_node(&graph, nodes[1]));
+
+        let order = Topological::new(&graph).collect::<Vec<_>>();
+        assert_eq!(order, [2, 3, 0]);
+        assert_eq!(toposort(&graph), Ok(order));
+        let components = strongly_connected(&graph);
+        assert_eq!(components, [vec![0], vec![3], vec![2]]);
+        let (_, component_of) = condensation(&graph, &components);
+        assert_eq!(component_of, [Some(0), None, Some(2), Some(1)]);
+
+        let order = PriorityTopological::lexicographic(&graph).collect::<Vec<_>>();
+        assert_eq!(order, [0, 2, 3]);
+        assert_eq!(AllTopological::new(&graph).count(), 3);
+    }
+
+    #[test]
+    fn test_index_type() {
+        let mut graph = StableGraph::<_, _, NonMaxU32>::default();
+        let a = graph.add_node('a');
+        let b = graph.add_node('b');
+        let ab = graph.add_edge(a, b, 1);
+        graph.remove_node(a);
+        assert_eq!(graph.add_node('c'), a);
+        assert!(!graph.contains_edge(ab));
+        assert_eq!(graph.add_edge(b, a, 2), ab);
+        assert_eq!(graph.parents(a).collect::<Vec<_>>(), [b]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut graph = StableGraph::<_, _, u16>::default();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.remove_node(b);
+        assert_eq!(
+            graph.try_add_edge(a, b, ()),
+            Err(Error::NodeOutOfRange {
+                node: b,
+                node_count: 2
+            })
+        );
+        let vacant = Error::NodeOutOfRange {
+            node: b,
+            node_count: 2,
+        };
+        assert_eq!(try_immediate_dominators(&graph, b), Err(vacant.clone()));
+        assert_eq!(try_frontiers(&graph, b), Err(vacant));
+        assert_eq!(try_immediate_dominators(&graph, a), Ok(vec![Some(a), None]));
+        assert_eq!(graph.try_add_edge(a, a, ()), Ok(0));
+        assert_eq!(graph.try_add_node(()), Ok(b));
+    }
+}
@@ -31,7 +31,7 @@ impl Direction for Undirected {
 /// A copyable reference to a graph.
 pub trait GraphRef: Copy {}
 
-impl<'graph, G> GraphRef for &'graph G {}
+impl<G> GraphRef for &G {}
 
 /// A trait for graphs where a node's children can be iterated over.
 pub trait Children: GraphRef {
@@ -67,6 +67,19 @@ pub trait Incoming<E>: GraphRef {
 
 /// A trait for graphs that have a known number of nodes.
 pub trait NodeCount: GraphRef {
-    /// Returns the number of nodes in the graph.
+    /// Returns the number of nodes in the graph, or an upper bound on the
+    /// node indices for graphs with vacant slots.
     fn node_count(self) -> usize;
+
+    /// Returns `true` if `node` is a node of the graph and not a vacant slot.
+    /// Algorithms that visit every node skip the indices this rejects.
+    fn contains_node(self, node: usize) -> bool {
+        node < self.node_count()
+    }
+}
+
+/// A trait for graphs that store a weight for each node.
+pub trait NodeWeights<N>: GraphRef {
+    /// Returns the weight of a node, or `None` if the node doesn't exist.
+    fn node_weight(self, node: usize) -> Option<N>;
 }
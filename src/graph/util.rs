@@ -31,6 +31,20 @@ pub fn to_linear_matrix_position<Ty: Direction>(row: usize, column: usize, width
     }
 }
 
+/// Returns the number of positions in a matrix holding `n_nodes` nodes,
+/// or `None` if it would overflow.
+#[must_use]
+#[inline]
+pub fn checked_linear_matrix_len<Ty: Direction>(n_nodes: usize) -> Option<usize> {
+    if Ty::is_directed() {
+        n_nodes.checked_mul(n_nodes)
+    } else {
+        n_nodes
+            .checked_mul(n_nodes.checked_add(1)?)
+            .map(|len| len / 2)
+    }
+}
+
 /// Extends a vector representing a square matrix to support holding `new_capacity` nodes.
 /// `old_node_capacity` is the number of nodes the matrix currently supports.
 /// `new_capacity` is the number of nodes the matrix should support after this function returns.
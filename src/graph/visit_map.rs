@@ -32,6 +32,6 @@ impl VisitMap {
     /// Returns `false` even for invalid nodes.
     #[must_use]
     pub fn is_visited(&self, node: usize) -> bool {
-        self.discovered.get(node).map_or(false, |&x| x)
+        self.discovered.get(node).is_some_and(|&x| x)
     }
 }
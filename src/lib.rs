@@ -1,7 +1,11 @@
 //! A graph theory library for Rust.
 extern crate alloc;
+pub mod components;
 pub mod dominance;
+mod error;
 pub mod graph;
 pub mod paths;
 pub mod traversal;
 pub mod util;
+
+pub use error::Error;
@@ -0,0 +1,192 @@
+//! A* search algorithm
+//! <https://en.wikipedia.org/wiki/A*_search_algorithm>
+use alloc::collections::BinaryHeap;
+use core::{hash::Hash, ops::Add};
+use std::collections::{hash_map::Entry, HashMap};
+
+use super::MinScored;
+use crate::graph::{traits::Outgoing, visit_map::VisitMap};
+
+/// Finds the cheapest path from `start` to a node for which `is_goal`
+/// returns `true`. Returns the cost of the path and its nodes, including both
+/// ends, or `None` if no goal can be reached.
+///
+/// `edge_cost` maps an edge's weight to its cost, which must not be negative.
+/// `heuristic` estimates the cost from a node to the nearest goal. It must
+/// never overestimate that cost, and must not decrease by more than the cost
+/// of an edge when moving along it, otherwise the path found may not be the
+/// cheapest.
+///
+/// With a heuristic that always returns zero this is Dijkstra's algorithm.
+pub fn astar<G, E, C, IsGoal, F, H>(
+    graph: G,
+    start: usize,
+    mut is_goal: IsGoal,
+    mut edge_cost: F,
+    mut heuristic: H,
+) -> Option<(C, Vec<usize>)>
+where
+    G: Outgoing<E>,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    IsGoal: FnMut(usize) -> bool,
+    F: FnMut(E) -> C,
+    H: FnMut(usize) -> C,
+{
+    search(
+        start,
+        |&node| is_goal(node),
+        |&node, successors| {
+            let edges = graph.outgoing(node);
+            successors.extend(edges.map(|(next, edge)| (next, edge_cost(edge))));
+        },
+        |&node| heuristic(node),
+    )
+}
+
+/// Finds the cheapest path from `start` to a goal, like [`astar`], on a graph
+/// that is only defined by its `successors`.
+///
+/// `successors` returns the neighbors of a node together with the cost of
+/// the edge to each of them. Nodes are discovered as the search goes, so the
+/// graph doesn't have to fit in memory, or even be finite.
+pub fn astar_implicit<N, C, IsGoal, S, I, H>(
+    start: N,
+    is_goal: IsGoal,
+    mut successors: S,
+    heuristic: H,
+) -> Option<(C, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    IsGoal: FnMut(&N) -> bool,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+{
+    search(
+        start,
+        is_goal,
+        |node, buffer| buffer.extend(successors(node)),
+        heuristic,
+    )
+}
+
+/// Runs A* with `expand` pushing the successors of a node into a buffer.
+fn search<N, C, IsGoal, X, H>(
+    start: N,
+    mut is_goal: IsGoal,
+    mut expand: X,
+    mut heuristic: H,
+) -> Option<(C, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    IsGoal: FnMut(&N) -> bool,
+    X: FnMut(&N, &mut Vec<(N, C)>),
+    H: FnMut(&N) -> C,
+{
+    // discovered nodes are numbered in discovery order
+    let mut nodes = vec![start.clone()];
+    let mut indices = HashMap::from([(start, 0)]);
+    // the cost of the cheapest known path and the node before it on the path
+    let mut costs = vec![C::default()];
+    let mut predecessors = vec![None];
+    let mut closed = VisitMap::default();
+    let mut heap = BinaryHeap::from([MinScored(heuristic(&nodes[0]), 0)]);
+    let mut buffer = Vec::new();
+
+    while let Some(MinScored(_, index)) = heap.pop() {
+        // skip stale heap entries
+        if !closed.visit(index) {
+            continue;
+        }
+        if is_goal(&nodes[index]) {
+            let mut path = vec![index];
+            while let Some(previous) = predecessors[*path.last()?] {
+                path.push(previous);
+            }
+            let path = path.into_iter().rev().map(|i| nodes[i].clone()).collect();
+            return Some((costs[index], path));
+        }
+
+        expand(&nodes[index], &mut buffer);
+        for (next, edge_cost) in buffer.drain(..) {
+            let next_cost = costs[index] + edge_cost;
+            let next = match indices.entry(next) {
+                Entry::Occupied(entry) => {
+                    let next = *entry.get();
+                    if closed.is_visited(next) || next_cost >= costs[next] {
+                        continue;
+                    }
+                    costs[next] = next_cost;
+                    predecessors[next] = Some(index);
+                    next
+                }
+                Entry::Vacant(entry) => {
+                    nodes.push(entry.key().clone());
+                    costs.push(next_cost);
+                    predecessors.push(Some(index));
+                    *entry.insert(nodes.len() - 1)
+                }
+            };
+            heap.push(MinScored(next_cost + heuristic(&nodes[next]), next));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, astar_implicit};
+    use crate::graph::adjlist::Graph;
+
+    #[test]
+    fn test_astar() {
+        let graph = Graph::from([
+            (0, 1, 7),
+            (0, 2, 9),
+            (0, 5, 14),
+            (1, 2, 10),
+            (1, 3, 15),
+            (2, 3, 11),
+            (2, 5, 2),
+            (3, 4, 6),
+            (5, 4, 9),
+        ]);
+        let path = astar(&graph, 0, |node| node == 4, |&cost| cost, |_| 0);
+        assert_eq!(path, Some((20, vec![0, 2, 5, 4])));
+        let path = astar(&graph, 4, |node| node == 0, |&cost| cost, |_| 0);
+        assert_eq!(path, None);
+        let path = astar(&graph, 3, |node| node == 3, |&cost| cost, |_| 0);
+        assert_eq!(path, Some((0, vec![3])));
+    }
+
+    #[test]
+    fn test_grid() {
+        // a wall at x = 2 with a gap at y = 4
+        let free =
+            |&(x, y): &(i32, i32)| (0..5).contains(&x) && (0..5).contains(&y) && (x != 2 || y == 4);
+        let goal = (4, 0);
+        let mut expanded = 0;
+        let path = astar_implicit(
+            (0, 0),
+            |&node| node == goal,
+            |&(x, y)| {
+                expanded += 1;
+                [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                    .into_iter()
+                    .filter(free)
+                    .map(|node| (node, 1))
+            },
+            |&(x, y)| (goal.0 - x).abs() + (goal.1 - y).abs(),
+        );
+        let Some((cost, path)) = path else {
+            panic!("no path to the goal");
+        };
+        assert_eq!(cost, 12);
+        assert_eq!(path.len(), 13);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(expanded < 24);
+    }
+}
@@ -0,0 +1,242 @@
+//! Bellman-Ford algorithm and the shortest path faster algorithm (SPFA)
+//! <https://en.wikipedia.org/wiki/Bellman%E2%80%93Ford_algorithm>
+//! <https://en.wikipedia.org/wiki/Shortest_path_faster_algorithm>
+use alloc::collections::VecDeque;
+use core::{fmt, ops::Add};
+
+use super::ShortestPaths;
+use crate::{
+    graph::traits::{NodeCount, Outgoing},
+    Error,
+};
+
+/// A cycle of edges with a negative total cost, reachable from the start node.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NegativeCycle(pub(crate) Vec<usize>);
+
+impl NegativeCycle {
+    /// Returns the nodes of the cycle in edge order. The last node has an edge
+    /// back to the first one.
+    #[must_use]
+    pub fn nodes(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl fmt::Display for NegativeCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "negative cycle through nodes {:?}", self.0)
+    }
+}
+
+impl std::error::Error for NegativeCycle {}
+
+impl From<NegativeCycle> for Error {
+    fn from(cycle: NegativeCycle) -> Self {
+        Self::NegativeCycle(cycle.0)
+    }
+}
+
+/// Finds the shortest paths from `start` to every node reachable from it.
+///
+/// Unlike [`dijkstra`](fn@super::dijkstra), edge costs may be negative.
+///
+/// Time complexity: **O(|V| |E|)**
+///
+/// # Errors
+///
+/// Returns the cycle if a negative cycle can be reached from `start`.
+///
+/// # Panics
+///
+/// Panics if `start` isn't a node in the graph.
+pub fn bellman_ford<G, E, C, F>(
+    graph: G,
+    start: usize,
+    edge_cost: F,
+) -> Result<ShortestPaths<C>, NegativeCycle>
+where
+    G: Outgoing<E> + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    let paths = ShortestPaths::new(start, graph.node_count(), C::default());
+    relax(graph, paths, edge_cost)
+}
+
+/// Runs the rounds of Bellman-Ford, starting from the distances in `paths`.
+pub(crate) fn relax<G, E, C, F>(
+    graph: G,
+    mut paths: ShortestPaths<C>,
+    mut edge_cost: F,
+) -> Result<ShortestPaths<C>, NegativeCycle>
+where
+    G: Outgoing<E> + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    let node_count = graph.node_count();
+    // a shortest path has at most |V| - 1 edges, so anything relaxed in
+    // round |V| lies on or behind a negative cycle
+    let mut relaxed = None;
+    for _ in 0..node_count {
+        relaxed = None;
+        for node in 0..node_count {
+            let Some(cost) = paths.distances[node] else {
+                continue;
+            };
+            for (next, edge) in graph.outgoing(node) {
+                let next_cost = cost + edge_cost(edge);
+                if paths.distances[next].is_none_or(|old| next_cost < old) {
+                    paths.distances[next] = Some(next_cost);
+                    paths.predecessors[next] = Some(node);
+                    relaxed = Some(next);
+                }
+            }
+        }
+        if relaxed.is_none() {
+            return Ok(paths);
+        }
+    }
+    match relaxed {
+        Some(node) => Err(find_cycle(&paths.predecessors, node)),
+        None => Ok(paths),
+    }
+}
+
+/// Finds the shortest paths from `start` to every node reachable from it,
+/// like [`bellman_ford`], but only relaxes the edges of nodes whose distance
+/// changed. Usually much faster on sparse graphs.
+///
+/// Time complexity: **O(|V| |E|)**
+///
+/// # Errors
+///
+/// Returns the cycle if a negative cycle can be reached from `start`.
+///
+/// # Panics
+///
+/// Panics if `start` isn't a node in the graph.
+pub fn spfa<G, E, C, F>(
+    graph: G,
+    start: usize,
+    mut edge_cost: F,
+) -> Result<ShortestPaths<C>, NegativeCycle>
+where
+    G: Outgoing<E> + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    let node_count = graph.node_count();
+    let mut paths = ShortestPaths::new(start, node_count, C::default());
+    // the number of edges on the current path to each node
+    let mut lengths = vec![0; node_count];
+    let mut queued = vec![false; node_count];
+    let mut queue = VecDeque::from([start]);
+    queued[start] = true;
+    while let Some(node) = queue.pop_front() {
+        queued[node] = false;
+        let Some(cost) = paths.distances[node] else {
+            continue;
+        };
+        for (next, edge) in graph.outgoing(node) {
+            let next_cost = cost + edge_cost(edge);
+            if paths.distances[next].is_none_or(|old| next_cost < old) {
+                paths.distances[next] = Some(next_cost);
+                paths.predecessors[next] = Some(node);
+                lengths[next] = lengths[node] + 1;
+                if lengths[next] >= node_count {
+                    // the predecessors may have moved on since the path was
+                    // built, so let Bellman-Ford recover the cycle itself
+                    return bellman_ford(graph, start, &mut edge_cost);
+                }
+                if !queued[next] {
+                    queued[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Returns the cycle in the predecessor graph behind `node`.
+///
+/// `node` must have been relaxed in the last round of Bellman-Ford, so
+/// walking back |V| steps from it is guaranteed to end up on the cycle.
+fn find_cycle(predecessors: &[Option<usize>], node: usize) -> NegativeCycle {
+    let mut node = node;
+    for _ in 0..predecessors.len() {
+        node = predecessors[node].unwrap_or(node);
+    }
+    let mut cycle = vec![node];
+    let mut current = predecessors[node].unwrap_or(node);
+    while current != node {
+        cycle.push(current);
+        current = predecessors[current].unwrap_or(node);
+    }
+    cycle.reverse();
+    NegativeCycle(cycle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bellman_ford, spfa};
+    use crate::{
+        graph::{adjlist::Graph, csr},
+        Error,
+    };
+
+    #[test]
+    fn test_bellman_ford() {
+        let graph = Graph::from([
+            (0, 1, 4),
+            (0, 2, 5),
+            (1, 2, -3),
+            (2, 3, 4),
+            (3, 1, 2),
+            (4, 0, 1),
+        ]);
+        for paths in [
+            bellman_ford(&graph, 0, |&cost: &i32| cost),
+            spfa(&graph, 0, |&cost: &i32| cost),
+        ] {
+            let paths = paths.unwrap_or_else(|cycle| panic!("{cycle}"));
+            assert_eq!(
+                paths.distances(),
+                [Some(0), Some(4), Some(1), Some(5), None]
+            );
+            assert_eq!(paths.path_to(3), Some(vec![0, 1, 2, 3]));
+        }
+    }
+
+    #[test]
+    fn test_difference_constraints() {
+        // x1 - x0 <= 3, x2 - x1 <= -2, x0 - x2 <= 1, x2 - x0 <= 0
+        // with a virtual source 3 connected to every variable
+        let graph = csr::Graph::from([
+            (0, 1, 3),
+            (1, 2, -2),
+            (2, 0, 1),
+            (0, 2, 0),
+            (3, 0, 0),
+            (3, 1, 0),
+            (3, 2, 0),
+        ]);
+        let paths = spfa(&graph, 3, |&cost: &i64| cost).unwrap_or_else(|cycle| panic!("{cycle}"));
+        assert_eq!(paths.distances(), [Some(-1), Some(0), Some(-2), Some(0)]);
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        let graph = Graph::from([(0, 1, 1), (1, 2, -1), (2, 3, -1), (3, 1, 1), (3, 4, 1)]);
+        let cycle = bellman_ford(&graph, 0, |&cost: &i32| cost).map(|_| ());
+        assert_eq!(cycle.map_err(|cycle| cycle.nodes().len()), Err(3));
+        let cycle = spfa(&graph, 0, |&cost: &i32| cost).err().map(Error::from);
+        assert!(matches!(cycle, Some(Error::NegativeCycle(nodes)) if nodes.len() == 3));
+
+        // the cycle can't be reached from 4
+        let paths = spfa(&graph, 4, |&cost: &i32| cost);
+        assert!(paths.is_ok());
+    }
+}
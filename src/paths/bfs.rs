@@ -0,0 +1,45 @@
+//! Shortest paths in unweighted graphs
+use super::ShortestPaths;
+use crate::{
+    graph::traits::{Children, NodeCount},
+    traversal::levelorder::Bfs,
+};
+
+/// Finds the paths with the fewest edges from `start` to every node reachable
+/// from it. The distance to a node is its number of edges.
+///
+/// Time complexity: **O(|V| + |E|)**
+///
+/// # Panics
+///
+/// Panics if `start` isn't a node in the graph.
+pub fn bfs_shortest_paths<G>(graph: G, start: usize) -> ShortestPaths<usize>
+where
+    G: Children + NodeCount,
+{
+    let mut paths = ShortestPaths::new(start, graph.node_count(), 0);
+    for (node, depth, parent) in Bfs::new(graph, start) {
+        paths.distances[node] = Some(depth);
+        paths.predecessors[node] = parent;
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bfs_shortest_paths;
+    use crate::graph::{matrix::Graph, traits::Directed};
+
+    #[test]
+    fn test_bfs_shortest_paths() {
+        let graph = Graph::<_, Directed>::from([(0, 1), (1, 2), (0, 3), (3, 2), (2, 4), (5, 0)]);
+        let paths = bfs_shortest_paths(&graph, 0);
+        assert_eq!(
+            paths.distances(),
+            [Some(0), Some(1), Some(2), Some(1), Some(3), None]
+        );
+        assert_eq!(paths.path_to(4), Some(vec![0, 1, 2, 4]));
+        assert_eq!(paths.path_to(5), None);
+        assert_eq!(paths.predecessor(0), None);
+    }
+}
@@ -0,0 +1,274 @@
+//! Point-to-point searches that expand from both ends and meet in the middle
+//! <https://en.wikipedia.org/wiki/Bidirectional_search>
+use alloc::collections::BinaryHeap;
+use core::ops::Add;
+use std::collections::{HashMap, HashSet};
+
+use super::MinScored;
+use crate::graph::traits::{Children, Incoming, Outgoing, Parents};
+
+/// Finds a path from `source` to `target` with the fewest edges. Returns its
+/// nodes, including both ends, or `None` if there is no path.
+///
+/// Expands a level from whichever side has the smaller frontier, so only the
+/// nodes around the two ends are visited instead of everything reachable
+/// from `source`.
+pub fn bidirectional_bfs<G>(graph: G, source: usize, target: usize) -> Option<Vec<usize>>
+where
+    G: Children + Parents,
+{
+    if source == target {
+        return Some(vec![source]);
+    }
+    // maps a discovered node to the node it was discovered from
+    let mut forward = HashMap::from([(source, source)]);
+    let mut backward = HashMap::from([(target, target)]);
+    let mut forward_frontier = vec![source];
+    let mut backward_frontier = vec![target];
+
+    while !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+        let meeting = if forward_frontier.len() <= backward_frontier.len() {
+            expand_level(&mut forward_frontier, &mut forward, &backward, |node| {
+                graph.children(node)
+            })
+        } else {
+            expand_level(&mut backward_frontier, &mut backward, &forward, |node| {
+                graph.parents(node)
+            })
+        };
+        if let Some(meeting) = meeting {
+            return Some(join(&forward, &backward, meeting));
+        }
+    }
+    None
+}
+
+/// Replaces `frontier` with the undiscovered neighbors of its nodes.
+/// Returns the first node that the other side has discovered too.
+fn expand_level<F, I>(
+    frontier: &mut Vec<usize>,
+    discovered: &mut HashMap<usize, usize>,
+    other: &HashMap<usize, usize>,
+    mut neighbors: F,
+) -> Option<usize>
+where
+    F: FnMut(usize) -> I,
+    I: Iterator<Item = usize>,
+{
+    let mut next = Vec::new();
+    for &node in frontier.iter() {
+        for neighbor in neighbors(node) {
+            if discovered.contains_key(&neighbor) {
+                continue;
+            }
+            discovered.insert(neighbor, node);
+            // the other side only holds nodes from its latest level and
+            // earlier, so the first meeting is on a shortest path
+            if other.contains_key(&neighbor) {
+                return Some(neighbor);
+            }
+            next.push(neighbor);
+        }
+    }
+    *frontier = next;
+    None
+}
+
+/// Finds the cheapest path from `source` to `target`. Returns its cost and
+/// nodes, including both ends, or `None` if there is no path.
+///
+/// Runs Dijkstra's algorithm forwards from `source` and backwards from
+/// `target`, always advancing the side with the cheaper next node, and
+/// stops once no cheaper path can be found. Edge costs must not be negative.
+pub fn bidirectional_dijkstra<G, E, C, F>(
+    graph: G,
+    source: usize,
+    target: usize,
+    mut edge_cost: F,
+) -> Option<(C, Vec<usize>)>
+where
+    G: Outgoing<E> + Incoming<E>,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    let mut forward = Side::<C>::new(source);
+    let mut backward = Side::<C>::new(target);
+    // the cheapest path found so far, and the node where its halves meet
+    let mut best = (source == target).then_some((C::default(), source));
+
+    while let (Some(next_forward), Some(next_backward)) = (forward.peek(), backward.peek()) {
+        if best.is_some_and(|(cost, _)| next_forward + next_backward >= cost) {
+            break;
+        }
+        if next_forward <= next_backward {
+            if let Some(node) = forward.settle() {
+                let edges = graph.outgoing(node);
+                forward.relax_all(node, edges, &backward, &mut edge_cost, &mut best);
+            }
+        } else if let Some(node) = backward.settle() {
+            let edges = graph.incoming(node);
+            backward.relax_all(node, edges, &forward, &mut edge_cost, &mut best);
+        }
+    }
+
+    let (cost, meeting) = best?;
+    Some((
+        cost,
+        join(&forward.predecessors, &backward.predecessors, meeting),
+    ))
+}
+
+/// One direction of a bidirectional Dijkstra search.
+struct Side<C> {
+    /// The discovered nodes, cheapest first.
+    heap: BinaryHeap<MinScored<C>>,
+    /// Maps a discovered node to the cost of the cheapest known path to it.
+    costs: HashMap<usize, C>,
+    /// Maps a discovered node to the node before it on that path.
+    predecessors: HashMap<usize, usize>,
+    /// The nodes whose cheapest path is final.
+    settled: HashSet<usize>,
+}
+
+impl<C: Copy + PartialOrd + Default> Side<C> {
+    /// Starts a search from `start`.
+    fn new(start: usize) -> Self {
+        Self {
+            heap: BinaryHeap::from([MinScored(C::default(), start)]),
+            costs: HashMap::from([(start, C::default())]),
+            predecessors: HashMap::from([(start, start)]),
+            settled: HashSet::new(),
+        }
+    }
+
+    /// Returns the cost of the next node to settle.
+    fn peek(&self) -> Option<C> {
+        self.heap.peek().map(|&MinScored(cost, _)| cost)
+    }
+
+    /// Settles the next node, or returns `None` if it was a stale entry.
+    fn settle(&mut self) -> Option<usize> {
+        let MinScored(_, node) = self.heap.pop()?;
+        self.settled.insert(node).then_some(node)
+    }
+
+    /// Relaxes the edges of a settled node, updating `best` when a path
+    /// through a neighbor that the `other` side has reached is cheaper.
+    fn relax_all<E, I, F>(
+        &mut self,
+        node: usize,
+        edges: I,
+        other: &Self,
+        edge_cost: &mut F,
+        best: &mut Option<(C, usize)>,
+    ) where
+        C: Add<Output = C>,
+        I: Iterator<Item = (usize, E)>,
+        F: FnMut(E) -> C,
+    {
+        let cost = self.costs[&node];
+        for (neighbor, edge) in edges {
+            self.relax(node, neighbor, cost + edge_cost(edge));
+            if let (Some(&there), Some(&back)) =
+                (self.costs.get(&neighbor), other.costs.get(&neighbor))
+            {
+                if best.is_none_or(|(cost, _)| there + back < cost) {
+                    *best = Some((there + back, neighbor));
+                }
+            }
+        }
+    }
+
+    /// Records a path to `node` through `from` if it's cheaper than the
+    /// known one.
+    fn relax(&mut self, from: usize, node: usize, cost: C) {
+        if self.settled.contains(&node) || self.costs.get(&node).is_some_and(|&old| cost >= old) {
+            return;
+        }
+        self.costs.insert(node, cost);
+        self.predecessors.insert(node, from);
+        self.heap.push(MinScored(cost, node));
+    }
+}
+
+/// Joins the forward path to `meeting` with the backward path from it.
+/// Both maps lead back to the end they started from, which maps to itself.
+fn join(
+    forward: &HashMap<usize, usize>,
+    backward: &HashMap<usize, usize>,
+    meeting: usize,
+) -> Vec<usize> {
+    let mut path = vec![meeting];
+    let mut node = meeting;
+    while let Some(&previous) = forward.get(&node).filter(|&&previous| previous != node) {
+        path.push(previous);
+        node = previous;
+    }
+    path.reverse();
+    let mut node = meeting;
+    while let Some(&next) = backward.get(&node).filter(|&&next| next != node) {
+        path.push(next);
+        node = next;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bidirectional_bfs, bidirectional_dijkstra};
+    use crate::{
+        graph::{adjlist::Graph, csr},
+        paths::dijkstra,
+    };
+
+    #[test]
+    fn test_bidirectional_bfs() {
+        let graph = Graph::from([
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (0, 5),
+            (5, 4),
+            (4, 6),
+            (7, 0),
+        ]);
+        assert_eq!(bidirectional_bfs(&graph, 0, 6), Some(vec![0, 5, 4, 6]));
+        assert_eq!(bidirectional_bfs(&graph, 1, 4), Some(vec![1, 2, 3, 4]));
+        assert_eq!(bidirectional_bfs(&graph, 3, 3), Some(vec![3]));
+        assert_eq!(bidirectional_bfs(&graph, 6, 0), None);
+        assert_eq!(bidirectional_bfs(&graph, 7, 1), Some(vec![7, 0, 1]));
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra() {
+        let graph = csr::Graph::from([
+            (0, 1, 7),
+            (0, 2, 9),
+            (0, 5, 14),
+            (1, 2, 10),
+            (1, 3, 15),
+            (2, 3, 11),
+            (2, 5, 2),
+            (3, 4, 6),
+            (5, 4, 9),
+            (4, 6, 1),
+        ]);
+        for source in 0..7 {
+            let paths = dijkstra(&graph, source, |&cost: &u32| cost);
+            for target in 0..7 {
+                let found = bidirectional_dijkstra(&graph, source, target, |&cost| cost);
+                assert_eq!(
+                    found.as_ref().map(|&(cost, _)| cost),
+                    paths.distance(target).copied()
+                );
+                if let Some((_, path)) = found {
+                    assert_eq!(path.first(), Some(&source));
+                    assert_eq!(path.last(), Some(&target));
+                }
+            }
+        }
+        let path = bidirectional_dijkstra(&graph, 0, 6, |&cost| cost);
+        assert_eq!(path, Some((21, vec![0, 2, 5, 4, 6])));
+    }
+}
@@ -0,0 +1,133 @@
+//! Shortest and longest paths in directed acyclic graphs
+//! <https://en.wikipedia.org/wiki/Longest_path_problem#Acyclic_graphs>
+use core::ops::Add;
+
+use super::ShortestPaths;
+use crate::{
+    graph::traits::{Children, NodeCount, Outgoing},
+    traversal::topological::toposort,
+    Error,
+};
+
+/// Finds the shortest paths from `start` to every node reachable from it in
+/// a directed acyclic graph. Edge costs may be negative.
+///
+/// Time complexity: **O(|V| + |E|)**
+///
+/// # Errors
+///
+/// Returns [`Error::CycleDetected`] if the graph contains a cycle.
+///
+/// # Panics
+///
+/// Panics if `start` isn't a node in the graph.
+pub fn dag_shortest_paths<G, E, C, F>(
+    graph: G,
+    start: usize,
+    edge_cost: F,
+) -> Result<ShortestPaths<C>, Error>
+where
+    G: Outgoing<E> + Children + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    relax_in_order(graph, start, edge_cost, |new, old| new < old)
+}
+
+/// Finds the longest paths from `start` to every node reachable from it in a
+/// directed acyclic graph, e.g. the critical path of a schedule.
+///
+/// Time complexity: **O(|V| + |E|)**
+///
+/// # Errors
+///
+/// Returns [`Error::CycleDetected`] if the graph contains a cycle.
+///
+/// # Panics
+///
+/// Panics if `start` isn't a node in the graph.
+pub fn dag_longest_paths<G, E, C, F>(
+    graph: G,
+    start: usize,
+    edge_cost: F,
+) -> Result<ShortestPaths<C>, Error>
+where
+    G: Outgoing<E> + Children + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    relax_in_order(graph, start, edge_cost, |new, old| new > old)
+}
+
+/// Relaxes every edge once, in topological order, keeping the cost that is
+/// `better`.
+fn relax_in_order<G, E, C, F, B>(
+    graph: G,
+    start: usize,
+    mut edge_cost: F,
+    better: B,
+) -> Result<ShortestPaths<C>, Error>
+where
+    G: Outgoing<E> + Children + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+    B: Fn(C, C) -> bool,
+{
+    let order = toposort(graph)?;
+
+    let mut paths = ShortestPaths::new(start, graph.node_count(), C::default());
+    for node in order {
+        let Some(cost) = paths.distances[node] else {
+            continue;
+        };
+        for (next, edge) in graph.outgoing(node) {
+            let next_cost = cost + edge_cost(edge);
+            if paths.distances[next].is_none_or(|old| better(next_cost, old)) {
+                paths.distances[next] = Some(next_cost);
+                paths.predecessors[next] = Some(node);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dag_longest_paths, dag_shortest_paths};
+    use crate::{graph::adjlist::Graph, Error};
+
+    #[test]
+    fn test_dag_paths() {
+        let graph = Graph::from([
+            (0, 1, 5),
+            (0, 2, 3),
+            (1, 3, 6),
+            (1, 2, 2),
+            (2, 4, 4),
+            (2, 5, 2),
+            (2, 3, 7),
+            (3, 4, -1),
+            (4, 5, -2),
+            (6, 0, 1),
+        ]);
+        let paths = dag_shortest_paths(&graph, 1, |&cost: &i32| cost);
+        let paths = paths.unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(
+            paths.distances(),
+            [None, Some(0), Some(2), Some(6), Some(5), Some(3), None]
+        );
+        assert_eq!(paths.path_to(4), Some(vec![1, 3, 4]));
+
+        let paths = dag_longest_paths(&graph, 0, |&cost: &i32| cost);
+        let paths = paths.unwrap_or_else(|err| panic!("{err}"));
+        assert_eq!(paths.distance(5), Some(&11));
+        assert_eq!(paths.path_to(5), Some(vec![0, 1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let graph = Graph::from([(0, 1, 1), (1, 2, 1), (2, 1, 1)]);
+        let paths = dag_longest_paths(&graph, 0, |&cost: &i32| cost);
+        assert_eq!(paths, Err(Error::CycleDetected(vec![1, 2])));
+    }
+}
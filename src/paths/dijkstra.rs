@@ -1,33 +1,159 @@
-//! Dijkstra algorithm.
-// use std::collections::BinaryHeap;
-
-// use crate::graph::traits::{Children, VertexCount};
-
-// /// Dijkstra's algorithm.
-// ///
-// /// # Panics
-// ///
-// /// Panics if `weights.len() != self.edge_count()`.
-// #[must_use]
-// pub fn dijkstra<G>(graph: G, start: usize) -> Vec<u64>
-// where
-//     G: Children + VertexCount,
-// {
-//     let mut dist = vec![u64::max_value(); graph.vertex_count()];
-//     let mut heap = BinaryHeap::new();
-
-//     dist[start] = 0;
-//     heap.push((Reverse(0), start));
-//     while let Some((Reverse(dist_u), u)) = heap.pop() {
-//         if dist[u] == dist_u {
-//             for (v, e) in self.neighbors(u) {
-//                 let alt_cost = dist_u.saturating_add(weights[e]);
-//                 if alt_cost < dist[v] {
-//                     dist[v] = alt_cost;
-//                     heap.push((Reverse(alt_cost), v));
-//                 }
-//             }
-//         }
-//     }
-//     dist
-// }
+//! Dijkstra's algorithm
+//! <https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm>
+use alloc::collections::BinaryHeap;
+use core::ops::Add;
+
+use super::{MinScored, ShortestPaths};
+use crate::graph::{
+    traits::{NodeCount, Outgoing},
+    visit_map::VisitMap,
+};
+
+/// Finds the shortest paths from `start` to every node reachable from it.
+///
+/// `edge_cost` maps an edge's weight to its cost, which must not be negative.
+/// The cost type's default value is used as the cost of an empty path.
+///
+/// Time complexity: **O((|V| + |E|) log |V|)**
+///
+/// # Panics
+///
+/// Panics if `start` isn't a node in the graph.
+pub fn dijkstra<G, E, C, F>(graph: G, start: usize, edge_cost: F) -> ShortestPaths<C>
+where
+    G: Outgoing<E> + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    search(graph, start, None, edge_cost, |_, _| true)
+}
+
+/// Finds the shortest path from `start` to `target`, like [`dijkstra`], but
+/// stops as soon as the shortest path to `target` is known.
+///
+/// Only the paths to `target` and to nodes closer than it are final.
+///
+/// # Panics
+///
+/// Panics if `start` isn't a node in the graph.
+pub fn dijkstra_until<G, E, C, F>(
+    graph: G,
+    start: usize,
+    target: usize,
+    edge_cost: F,
+) -> ShortestPaths<C>
+where
+    G: Outgoing<E> + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    search(graph, start, Some(target), edge_cost, |_, _| true)
+}
+
+/// Runs Dijkstra's algorithm until the heap is empty or `target` is finished.
+///
+/// Only follows the edges from `a` to `b` for which `allowed(a, b)` returns
+/// `true`, which masks nodes and edges out of the graph.
+pub(crate) fn search<G, E, C, F, A>(
+    graph: G,
+    start: usize,
+    target: Option<usize>,
+    mut edge_cost: F,
+    mut allowed: A,
+) -> ShortestPaths<C>
+where
+    G: Outgoing<E> + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+    A: FnMut(usize, usize) -> bool,
+{
+    let mut paths = ShortestPaths::new(start, graph.node_count(), C::default());
+    let mut finished = VisitMap::default();
+    let mut heap = BinaryHeap::new();
+    heap.push(MinScored(C::default(), start));
+    while let Some(MinScored(cost, node)) = heap.pop() {
+        // skip stale heap entries
+        if !finished.visit(node) {
+            continue;
+        }
+        if Some(node) == target {
+            break;
+        }
+        for (next, edge) in graph.outgoing(node) {
+            if finished.is_visited(next) || !allowed(node, next) {
+                continue;
+            }
+            let next_cost = cost + edge_cost(edge);
+            if paths.distances[next].is_none_or(|old| next_cost < old) {
+                paths.distances[next] = Some(next_cost);
+                paths.predecessors[next] = Some(node);
+                heap.push(MinScored(next_cost, next));
+            }
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dijkstra, dijkstra_until};
+    use crate::{
+        graph::{adjlist, matrix, traits::Undirected},
+        Error,
+    };
+
+    #[test]
+    fn test_dijkstra() {
+        let graph = adjlist::Graph::from([(0, 1), (1, 2), (2, 0)]).with_weights(vec![7, 3, 5]);
+        let paths = dijkstra(&graph, 0, |&weight: &u64| weight);
+        assert_eq!(paths.distances(), [Some(0), Some(7), Some(10)]);
+
+        let graph = adjlist::Graph::from([
+            (1, 2, 3),
+            (1, 3, 8),
+            (1, 5, 2),
+            (2, 4, 1),
+            (2, 5, 7),
+            (3, 2, 4),
+            (4, 1, 2),
+            (4, 3, 2),
+            (5, 4, 6),
+        ]);
+        let paths = dijkstra(&graph, 1, |&weight| weight);
+        assert_eq!(
+            paths.distances(),
+            [None, Some(0), Some(3), Some(6), Some(4), Some(2)]
+        );
+        assert_eq!(paths.path_to(3), Some(vec![1, 2, 4, 3]));
+        assert_eq!(paths.path_to(1), Some(vec![1]));
+        assert_eq!(paths.path_to(0), None);
+        assert_eq!(paths.predecessor(4), Some(2));
+        assert_eq!(paths.try_path_to(3), Ok(vec![1, 2, 4, 3]));
+        assert_eq!(paths.try_path_to(0), Err(Error::UnreachableNode(0)));
+        assert_eq!(
+            paths.try_path_to(6),
+            Err(Error::NodeOutOfRange {
+                node: 6,
+                node_count: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_until() {
+        let graph = matrix::Graph::<_, Undirected>::from([
+            (0, 1, 1.5),
+            (1, 2, 1.0),
+            (0, 2, 3.0),
+            (2, 3, 4.0),
+        ]);
+        let paths = dijkstra_until(&graph, 0, 2, |&weight| weight);
+        assert_eq!(paths.distance(2), Some(&2.5));
+        assert_eq!(paths.path_to(2), Some(vec![0, 1, 2]));
+        // 3 is further away than the target so it isn't reached
+        assert_eq!(paths.distance(3), None);
+
+        let paths = dijkstra(&graph, 3, |&weight| weight);
+        assert_eq!(paths.path_to(0), Some(vec![3, 2, 1, 0]));
+    }
+}
@@ -0,0 +1,137 @@
+//! Floyd-Warshall algorithm
+//! <https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm>
+use core::ops::Add;
+
+use super::{AllPairs, NegativeCycle};
+use crate::graph::{
+    traits::{NodeCount, Outgoing},
+    visit_map::VisitMap,
+};
+
+/// Finds the shortest paths between every pair of nodes.
+///
+/// Edge costs may be negative. Works best on dense graphs, like
+/// [`matrix::Graph`](crate::graph::matrix::Graph); see
+/// [`johnson`](fn@super::johnson) for sparse graphs.
+///
+/// Time complexity: **O(|V|^3)**
+///
+/// # Errors
+///
+/// Returns a negative cycle if the graph contains one.
+pub fn floyd_warshall<G, E, C, F>(graph: G, mut edge_cost: F) -> Result<AllPairs<C>, NegativeCycle>
+where
+    G: Outgoing<E> + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    let n = graph.node_count();
+    let mut distances = vec![None; n * n];
+    let mut next = vec![None; n * n];
+    for node in 0..n {
+        distances[node * n + node] = Some(C::default());
+        next[node * n + node] = Some(node);
+    }
+    for from in 0..n {
+        for (to, edge) in graph.outgoing(from) {
+            let cost = edge_cost(edge);
+            // keep the cheapest of parallel edges
+            if distances[from * n + to].is_none_or(|old| cost < old) {
+                distances[from * n + to] = Some(cost);
+                next[from * n + to] = Some(to);
+            }
+        }
+    }
+
+    for via in 0..n {
+        // A negative cycle is found before the iteration of its largest
+        // node. Stopping here keeps distances from shrinking without bound.
+        if distances[via * n + via].is_some_and(|cost| cost < C::default()) {
+            return Err(find_cycle(&next, n, via));
+        }
+        for from in 0..n {
+            let Some(first) = distances[from * n + via] else {
+                continue;
+            };
+            for to in 0..n {
+                let Some(second) = distances[via * n + to] else {
+                    continue;
+                };
+                let cost = first + second;
+                if distances[from * n + to].is_none_or(|old| cost < old) {
+                    distances[from * n + to] = Some(cost);
+                    next[from * n + to] = next[from * n + via];
+                }
+            }
+        }
+    }
+    Ok(AllPairs {
+        node_count: n,
+        distances,
+        next,
+    })
+}
+
+/// Follows the next hops towards `node` until a node repeats, returning the
+/// loop that was found.
+fn find_cycle(next: &[Option<usize>], n: usize, node: usize) -> NegativeCycle {
+    let mut visited = VisitMap::with_capacity(n);
+    let mut path = Vec::new();
+    let mut current = node;
+    while visited.visit(current) {
+        path.push(current);
+        current = next[current * n + node].unwrap_or(node);
+    }
+    let start = path.iter().position(|&n| n == current).unwrap_or(0);
+    NegativeCycle(path.split_off(start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{floyd_warshall, NegativeCycle};
+    use crate::graph::{
+        matrix::Graph,
+        traits::{Directed, Undirected},
+    };
+
+    #[test]
+    fn test_floyd_warshall() {
+        let graph =
+            Graph::<_, Directed>::from([(0, 2, -2), (2, 3, 2), (3, 1, -1), (1, 0, 4), (1, 2, 3)]);
+        let paths =
+            floyd_warshall(&graph, |&cost: &i32| cost).unwrap_or_else(|cycle| panic!("{cycle}"));
+        assert_eq!(
+            paths.distances_from(1),
+            [Some(4), Some(0), Some(2), Some(4)]
+        );
+        assert_eq!(paths.distance(0, 1), Some(&-1));
+        assert_eq!(paths.path(0, 1), Some(vec![0, 2, 3, 1]));
+        assert_eq!(paths.next_hop(1, 3), Some(0));
+        assert_eq!(paths.path(2, 2), Some(vec![2]));
+
+        let graph = Graph::<_, Undirected>::from([(0, 1, 5), (1, 2, 1), (3, 3, 0)]);
+        let paths =
+            floyd_warshall(&graph, |&cost: &u32| cost).unwrap_or_else(|cycle| panic!("{cycle}"));
+        assert_eq!(paths.path(2, 0), Some(vec![2, 1, 0]));
+        assert_eq!(paths.distance(0, 3), None);
+        assert_eq!(paths.path(0, 3), None);
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        let graph = Graph::<_, Directed>::from([(0, 1, 1), (1, 2, -3), (2, 1, 1), (2, 3, 1)]);
+        let Err(NegativeCycle(mut nodes)) = floyd_warshall(&graph, |&cost: &i32| cost) else {
+            panic!("expected a negative cycle");
+        };
+        nodes.sort_unstable();
+        assert_eq!(nodes, [1, 2]);
+
+        // integer costs would overflow if the cycle was only found at the end
+        let graph = (0..40)
+            .flat_map(|from| (0..40).map(move |to| (from, to)))
+            .filter(|(from, to)| from != to)
+            .collect::<Graph<(), Directed>>();
+        let cycle = floyd_warshall(&graph, |_| -1_i32).map(|_| ());
+        assert!(cycle.is_err());
+    }
+}
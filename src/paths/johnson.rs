@@ -0,0 +1,142 @@
+//! Johnson's algorithm
+//! <https://en.wikipedia.org/wiki/Johnson%27s_algorithm>
+use core::ops::{Add, Sub};
+
+use super::{bellman_ford::relax, dijkstra, AllPairs, NegativeCycle, ShortestPaths};
+use crate::graph::{
+    csr,
+    traits::{NodeCount, Outgoing},
+};
+
+/// Finds the shortest paths between every pair of nodes.
+///
+/// Edge costs may be negative. Bellman-Ford computes a potential for every
+/// node, which is used to make all edge costs non-negative, before running
+/// Dijkstra's algorithm from every node. Faster than
+/// [`floyd_warshall`](fn@super::floyd_warshall) on sparse graphs.
+///
+/// Time complexity: **O(|V| |E| log |V|)**
+///
+/// # Errors
+///
+/// Returns a negative cycle if the graph contains one.
+pub fn johnson<G, E, C, F>(graph: G, mut edge_cost: F) -> Result<AllPairs<C>, NegativeCycle>
+where
+    G: Outgoing<E> + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Sub<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    let n = graph.node_count();
+    let edges = (0..n)
+        .flat_map(|from| graph.outgoing(from).map(move |(to, edge)| (from, to, edge)))
+        .map(|(from, to, edge)| (from, to, edge_cost(edge)))
+        .collect::<Vec<_>>();
+
+    // starting with every node at distance zero is the same as adding a
+    // virtual node with a zero cost edge to every node
+    let potentials = ShortestPaths {
+        start: 0,
+        distances: vec![Some(C::default()); n],
+        predecessors: vec![None; n],
+    };
+    let potentials = relax(
+        &csr::Graph::<C>::with_node_count(n, edges.iter().copied()),
+        potentials,
+        |&cost| cost,
+    )?
+    .distances
+    .into_iter()
+    .map(Option::unwrap_or_default)
+    .collect::<Vec<_>>();
+
+    let reweighted = csr::Graph::<C>::with_node_count(
+        n,
+        edges
+            .into_iter()
+            .map(|(from, to, cost)| (from, to, cost + potentials[from] - potentials[to])),
+    );
+    let mut distances = Vec::with_capacity(n * n);
+    let mut next = Vec::with_capacity(n * n);
+    for from in 0..n {
+        let paths = dijkstra(&reweighted, from, |&cost| cost);
+        next.extend(next_hops(&paths));
+        distances.extend(
+            paths
+                .distances
+                .into_iter()
+                .enumerate()
+                .map(|(to, cost)| Some(cost? + potentials[to] - potentials[from])),
+        );
+    }
+    Ok(AllPairs {
+        node_count: n,
+        distances,
+        next,
+    })
+}
+
+/// Converts a shortest path tree to the first node on the path to each node.
+fn next_hops<C>(paths: &ShortestPaths<C>) -> Vec<Option<usize>> {
+    let mut next = vec![None; paths.distances.len()];
+    next[paths.start] = Some(paths.start);
+    for node in 0..next.len() {
+        // walk up the tree to the closest node with a known next hop
+        let mut chain = Vec::new();
+        let mut current = node;
+        while next[current].is_none() {
+            let Some(predecessor) = paths.predecessor(current) else {
+                break;
+            };
+            chain.push(current);
+            current = predecessor;
+        }
+        let mut parent = current;
+        for &node in chain.iter().rev() {
+            next[node] = if parent == paths.start {
+                Some(node)
+            } else {
+                next[parent]
+            };
+            parent = node;
+        }
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::johnson;
+    use crate::{
+        graph::{adjlist::Graph, matrix, traits::Directed},
+        paths::floyd_warshall,
+    };
+
+    #[test]
+    fn test_johnson() {
+        let edges = [
+            (0, 2, -2),
+            (2, 3, 2),
+            (3, 1, -1),
+            (1, 0, 4),
+            (1, 2, 3),
+            (4, 1, 1),
+        ];
+        let graph = Graph::from(edges);
+        let paths = johnson(&graph, |&cost: &i32| cost).unwrap_or_else(|cycle| panic!("{cycle}"));
+        let expected = floyd_warshall(&matrix::Graph::<_, Directed>::from(edges), |&cost| cost);
+        assert_eq!(Ok(&paths), expected.as_ref());
+        assert_eq!(paths.path(4, 3), Some(vec![4, 1, 0, 2, 3]));
+        assert_eq!(paths.distance(4, 3), Some(&5));
+        assert_eq!(paths.path(3, 4), None);
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        let graph = Graph::from([(0, 1, 1), (1, 2, -3), (2, 1, 1), (2, 3, 1)]);
+        let cycle = johnson(&graph, |&cost: &i32| cost).err();
+        assert_eq!(cycle.map(|cycle| cycle.nodes().len()), Some(2));
+
+        let graph = Graph::<i32>::new();
+        assert!(johnson(&graph, |&cost| cost).is_ok());
+    }
+}
@@ -1,2 +1,214 @@
 //! Path finding algorithms.
+use core::cmp::Ordering;
+
+use crate::Error;
+
+pub mod astar;
+pub mod bellman_ford;
+pub mod bfs;
+pub mod bidirectional;
+pub mod dag;
 pub mod dijkstra;
+pub mod floyd_warshall;
+pub mod johnson;
+pub mod yen;
+
+pub use astar::{astar, astar_implicit};
+pub use bellman_ford::{bellman_ford, spfa, NegativeCycle};
+pub use bfs::bfs_shortest_paths;
+pub use bidirectional::{bidirectional_bfs, bidirectional_dijkstra};
+pub use dag::{dag_longest_paths, dag_shortest_paths};
+pub use dijkstra::{dijkstra, dijkstra_until};
+pub use floyd_warshall::floyd_warshall;
+pub use johnson::johnson;
+pub use yen::k_shortest_paths;
+
+/// The shortest paths from a start node to every other node of a graph.
+///
+/// Nodes that weren't reached have no distance and no predecessor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths<C> {
+    /// The node that the paths start from.
+    pub(crate) start: usize,
+    /// Maps a node to the cost of the shortest path to it.
+    pub(crate) distances: Vec<Option<C>>,
+    /// Maps a node to the node before it on the shortest path to it.
+    pub(crate) predecessors: Vec<Option<usize>>,
+}
+
+impl<C> ShortestPaths<C> {
+    /// Constructs the paths of a search where only `start` has been reached.
+    pub(crate) fn new(start: usize, node_count: usize, zero: C) -> Self
+    where
+        C: Clone,
+    {
+        let mut distances = vec![None; node_count];
+        distances[start] = Some(zero);
+        Self {
+            start,
+            distances,
+            predecessors: vec![None; node_count],
+        }
+    }
+
+    /// Returns the node that the paths start from.
+    #[must_use]
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the cost of the shortest path to each node.
+    #[must_use]
+    pub fn distances(&self) -> &[Option<C>] {
+        &self.distances
+    }
+
+    /// Returns the node before each node on its shortest path.
+    #[must_use]
+    pub fn predecessors(&self) -> &[Option<usize>] {
+        &self.predecessors
+    }
+
+    /// Returns the cost of the shortest path to `node`, or `None` if the node
+    /// wasn't reached.
+    #[must_use]
+    pub fn distance(&self, node: usize) -> Option<&C> {
+        self.distances.get(node)?.as_ref()
+    }
+
+    /// Returns the node before `node` on its shortest path, or `None` if the
+    /// node is the start node or wasn't reached.
+    #[must_use]
+    pub fn predecessor(&self, node: usize) -> Option<usize> {
+        self.predecessors.get(node).copied().flatten()
+    }
+
+    /// Returns the nodes on the shortest path from the start node to `node`,
+    /// including both ends. Returns `None` if the node wasn't reached.
+    #[must_use]
+    pub fn path_to(&self, node: usize) -> Option<Vec<usize>> {
+        self.distance(node)?;
+        let mut path = vec![node];
+        let mut current = node;
+        while current != self.start {
+            current = self.predecessor(current)?;
+            path.push(current);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns the nodes on the shortest path from the start node to `node`,
+    /// like [`ShortestPaths::path_to`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NodeOutOfRange`] if `node` isn't a node in the graph and
+    /// [`Error::UnreachableNode`] if it wasn't reached.
+    pub fn try_path_to(&self, node: usize) -> Result<Vec<usize>, Error> {
+        let node_count = self.distances.len();
+        if node >= node_count {
+            return Err(Error::NodeOutOfRange { node, node_count });
+        }
+        self.path_to(node).ok_or(Error::UnreachableNode(node))
+    }
+}
+
+/// The shortest paths between every pair of nodes of a graph.
+///
+/// Pairs of nodes without a path between them have no distance and no next hop.
+///
+/// Space complexity: **O(|V|^2)**
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllPairs<C> {
+    /// The number of nodes in the graph.
+    node_count: usize,
+    /// Maps a pair of nodes to the cost of the shortest path between them,
+    /// stored row by row.
+    distances: Vec<Option<C>>,
+    /// Maps a pair of nodes to the node after the first one on the shortest
+    /// path between them, stored row by row.
+    next: Vec<Option<usize>>,
+}
+
+impl<C> AllPairs<C> {
+    /// Returns the number of nodes in the graph.
+    #[must_use]
+    pub const fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Returns the cost of the shortest path from `from` to every node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` isn't a node in the graph.
+    #[must_use]
+    pub fn distances_from(&self, from: usize) -> &[Option<C>] {
+        &self.distances[from * self.node_count..(from + 1) * self.node_count]
+    }
+
+    /// Returns the cost of the shortest path from `from` to `to`, or `None`
+    /// if there is no path.
+    #[must_use]
+    pub fn distance(&self, from: usize, to: usize) -> Option<&C> {
+        self.distances.get(self.position(from, to)?)?.as_ref()
+    }
+
+    /// Returns the node after `from` on the shortest path from `from` to `to`,
+    /// or `None` if there is no path.
+    #[must_use]
+    pub fn next_hop(&self, from: usize, to: usize) -> Option<usize> {
+        self.next.get(self.position(from, to)?).copied().flatten()
+    }
+
+    /// Returns the nodes on the shortest path from `from` to `to`, including
+    /// both ends. Returns `None` if there is no path.
+    #[must_use]
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        self.distance(from, to)?;
+        let mut path = vec![from];
+        let mut current = from;
+        while current != to {
+            current = self.next_hop(current, to)?;
+            path.push(current);
+        }
+        Some(path)
+    }
+
+    /// Returns the position of a pair of nodes in the matrices.
+    const fn position(&self, from: usize, to: usize) -> Option<usize> {
+        if from < self.node_count && to < self.node_count {
+            Some(from * self.node_count + to)
+        } else {
+            None
+        }
+    }
+}
+
+/// A node and the cost to reach it, ordered by cost.
+///
+/// The ordering is reversed so that a max-heap pops the cheapest node first.
+/// Incomparable costs, like NaN, are considered equal.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MinScored<C>(pub C, pub usize);
+
+impl<C: PartialOrd> Ord for MinScored<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<C: PartialOrd> PartialOrd for MinScored<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: PartialOrd> PartialEq for MinScored<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: PartialOrd> Eq for MinScored<C> {}
@@ -0,0 +1,176 @@
+//! Yen's k shortest loopless paths algorithm
+//! <https://en.wikipedia.org/wiki/Yen%27s_algorithm>
+use core::{cmp::Ordering, ops::Add};
+
+use super::dijkstra::search;
+use crate::graph::traits::{NodeCount, Outgoing};
+
+/// Finds up to `k` of the cheapest paths from `source` to `target` that don't
+/// visit any node twice, ordered by cost. Returns the cost of each path and
+/// its nodes, including both ends.
+///
+/// Paths are compared by their nodes, so parallel edges don't produce
+/// additional paths. Edge costs must not be negative.
+///
+/// Time complexity: **O(k |V| (|V| + |E|) log |V|)**
+///
+/// # Panics
+///
+/// Panics if `source` isn't a node in the graph.
+pub fn k_shortest_paths<G, E, C, F>(
+    graph: G,
+    source: usize,
+    target: usize,
+    k: usize,
+    mut edge_cost: F,
+) -> Vec<(C, Vec<usize>)>
+where
+    G: Outgoing<E> + NodeCount,
+    C: Copy + PartialOrd + Add<Output = C> + Default,
+    F: FnMut(E) -> C,
+{
+    let mut paths: Vec<(C, Vec<usize>)> = Vec::new();
+    if k == 0 {
+        return paths;
+    }
+    let shortest = search(graph, source, Some(target), &mut edge_cost, |_, _| true);
+    let (Some(&cost), Some(path)) = (shortest.distance(target), shortest.path_to(target)) else {
+        return paths;
+    };
+    paths.push((cost, path));
+
+    let mut candidates: Vec<(C, Vec<usize>)> = Vec::new();
+    let mut removed_nodes = vec![false; graph.node_count()];
+    while paths.len() < k {
+        let Some((_, previous)) = paths.last() else {
+            break;
+        };
+        let previous = previous.clone();
+        let mut root_cost = C::default();
+        // branch off the previous path at every node but the target
+        for (i, &spur) in previous.iter().enumerate().take(previous.len() - 1) {
+            let root = &previous[..=i];
+            // don't repeat the edges that known paths with the same root take
+            let removed_edges = paths
+                .iter()
+                .filter(|(_, path)| path.len() > i + 1 && path[..=i] == *root)
+                .map(|(_, path)| (path[i], path[i + 1]))
+                .collect::<Vec<_>>();
+            // and don't go back through the root
+            for &node in &root[..i] {
+                removed_nodes[node] = true;
+            }
+
+            let spur_paths = search(graph, spur, Some(target), &mut edge_cost, |a, b| {
+                !removed_nodes[b] && !removed_edges.contains(&(a, b))
+            });
+            if let (Some(&spur_cost), Some(spur_path)) =
+                (spur_paths.distance(target), spur_paths.path_to(target))
+            {
+                let path = root[..i]
+                    .iter()
+                    .copied()
+                    .chain(spur_path)
+                    .collect::<Vec<_>>();
+                if !candidates
+                    .iter()
+                    .chain(&paths)
+                    .any(|(_, known)| *known == path)
+                {
+                    candidates.push((root_cost + spur_cost, path));
+                }
+            }
+
+            for &node in &root[..i] {
+                removed_nodes[node] = false;
+            }
+            match cheapest_edge(graph, spur, previous[i + 1], &mut edge_cost) {
+                Some(cost) => root_cost = root_cost + cost,
+                None => break,
+            }
+        }
+
+        // move the cheapest candidate to the results
+        let Some(best) = (0..candidates.len()).min_by(|&a, &b| {
+            candidates[a]
+                .0
+                .partial_cmp(&candidates[b].0)
+                .unwrap_or(Ordering::Equal)
+        }) else {
+            break;
+        };
+        paths.push(candidates.swap_remove(best));
+    }
+    paths
+}
+
+/// Returns the cost of the cheapest edge from `a` to `b`.
+fn cheapest_edge<G, E, C, F>(graph: G, a: usize, b: usize, edge_cost: &mut F) -> Option<C>
+where
+    G: Outgoing<E>,
+    C: PartialOrd,
+    F: FnMut(E) -> C,
+{
+    graph
+        .outgoing(a)
+        .filter(|&(node, _)| node == b)
+        .map(|(_, edge)| edge_cost(edge))
+        .min_by(|x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::k_shortest_paths;
+    use crate::graph::{adjlist::Graph, matrix, traits::Undirected};
+
+    #[test]
+    fn test_k_shortest_paths() {
+        // from the wikipedia article, with C..H numbered 0..5
+        let graph = Graph::from([
+            (0, 1, 3),
+            (0, 2, 2),
+            (1, 3, 4),
+            (2, 1, 1),
+            (2, 3, 2),
+            (2, 4, 3),
+            (3, 4, 2),
+            (3, 5, 1),
+            (4, 5, 2),
+        ]);
+        let paths = k_shortest_paths(&graph, 0, 5, 3, |&cost: &u32| cost);
+        assert_eq!(
+            paths,
+            [
+                (5, vec![0, 2, 3, 5]),
+                (7, vec![0, 2, 4, 5]),
+                (8, vec![0, 1, 3, 5]),
+            ]
+        );
+        let paths = k_shortest_paths(&graph, 0, 5, 10, |&cost: &u32| cost);
+        assert_eq!(paths.len(), 7);
+        assert!(paths.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert!(k_shortest_paths(&graph, 5, 0, 3, |&cost: &u32| cost).is_empty());
+        assert!(k_shortest_paths(&graph, 0, 5, 0, |&cost: &u32| cost).is_empty());
+    }
+
+    #[test]
+    fn test_undirected() {
+        let graph = matrix::Graph::<_, Undirected>::from([
+            (0, 1, 1.0),
+            (1, 3, 1.0),
+            (0, 2, 1.5),
+            (2, 3, 1.0),
+            (1, 2, 0.25),
+        ]);
+        let paths = k_shortest_paths(&graph, 0, 3, 5, |&cost: &f64| cost);
+        assert_eq!(
+            paths,
+            [
+                (2.0, vec![0, 1, 3]),
+                (2.25, vec![0, 1, 2, 3]),
+                (2.5, vec![0, 2, 3]),
+                (2.75, vec![0, 2, 1, 3]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Depth-first search with events
+//! <https://en.wikipedia.org/wiki/Depth-first_search>
+
+use crate::graph::{traits::Children, visit_map::VisitMap};
+
+/// An event emitted by [`depth_first_search`].
+///
+/// Discovery and finish times come from a single counter, so a node `u` is
+/// an ancestor of `v` in the search forest if and only if `u` was discovered
+/// before `v` and finished after it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DfsEvent {
+    /// A node was reached for the first time, at the given time.
+    Discover(usize, usize),
+    /// An edge to an undiscovered node, which becomes a child in the search tree.
+    TreeEdge(usize, usize),
+    /// An edge to an ancestor that hasn't finished yet, which closes a cycle.
+    /// In undirected graphs the edge back to the parent is reported as well.
+    BackEdge(usize, usize),
+    /// An edge to a finished node: either a descendant or a node in an
+    /// earlier subtree.
+    CrossForwardEdge(usize, usize),
+    /// All of a node's edges have been explored, at the given time.
+    Finish(usize, usize),
+}
+
+/// Controls how [`depth_first_search`] continues after an event.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Control<B> {
+    /// Continue the search.
+    Continue,
+    /// After `Discover`, don't explore the node's edges. After `TreeEdge`,
+    /// don't discover the target. Acts like `Continue` for other events.
+    Prune,
+    /// Stop the search and return the value.
+    Break(B),
+}
+
+impl<B> Control<B> {
+    /// Returns the value the search was stopped with, if any.
+    #[must_use]
+    pub fn break_value(self) -> Option<B> {
+        match self {
+            Self::Break(value) => Some(value),
+            Self::Continue | Self::Prune => None,
+        }
+    }
+}
+
+/// The values a visitor can return to control the search.
+///
+/// Implemented for `()`, for visitors that never stop the search early.
+pub trait ControlFlow {
+    /// Returns the value that continues the search.
+    fn continuing() -> Self;
+    /// Returns `true` if the search should stop.
+    fn should_break(&self) -> bool;
+    /// Returns `true` if the search shouldn't go past the current node or edge.
+    fn should_prune(&self) -> bool;
+}
+
+impl ControlFlow for () {
+    fn continuing() {}
+
+    fn should_break(&self) -> bool {
+        false
+    }
+
+    fn should_prune(&self) -> bool {
+        false
+    }
+}
+
+impl<B> ControlFlow for Control<B> {
+    fn continuing() -> Self {
+        Self::Continue
+    }
+
+    fn should_break(&self) -> bool {
+        matches!(self, Self::Break(_))
+    }
+
+    fn should_prune(&self) -> bool {
+        matches!(self, Self::Prune)
+    }
+}
+
+/// Returns from the search if the visitor asked to break.
+macro_rules! try_control {
+    ($control:expr) => {{
+        let control = $control;
+        if control.should_break() {
+            return control;
+        }
+        control
+    }};
+}
+
+/// Runs a depth-first search from each of the roots in turn, skipping roots
+/// that were already discovered, and calls `visitor` for every event.
+///
+/// Returns the value that the visitor stopped the search with, or the
+/// continuing value if the search ran to completion.
+///
+/// The search is iterative, so deep graphs don't overflow the call stack.
+///
+/// Time complexity: **O(|V| + |E|)**
+pub fn depth_first_search<G, I, F, C>(graph: G, roots: I, mut visitor: F) -> C
+where
+    G: Children,
+    I: IntoIterator<Item = usize>,
+    F: FnMut(DfsEvent) -> C,
+    C: ControlFlow,
+{
+    let mut discovered = VisitMap::default();
+    let mut finished = VisitMap::default();
+    let mut time = 0;
+    // the nodes on the current path, with the edges left to explore
+    let mut stack: Vec<(usize, G::Iter)> = Vec::new();
+
+    for root in roots {
+        if !discovered.visit(root) {
+            continue;
+        }
+        time += 1;
+        if try_control!(visitor(DfsEvent::Discover(root, time))).should_prune() {
+            finished.visit(root);
+            time += 1;
+            try_control!(visitor(DfsEvent::Finish(root, time)));
+            continue;
+        }
+        stack.push((root, graph.children(root)));
+
+        while let Some((node, children)) = stack.last_mut() {
+            let node = *node;
+            let Some(child) = children.next() else {
+                stack.pop();
+                finished.visit(node);
+                time += 1;
+                try_control!(visitor(DfsEvent::Finish(node, time)));
+                continue;
+            };
+
+            if discovered.is_visited(child) {
+                let event = if finished.is_visited(child) {
+                    DfsEvent::CrossForwardEdge(node, child)
+                } else {
+                    DfsEvent::BackEdge(node, child)
+                };
+                try_control!(visitor(event));
+                continue;
+            }
+            if try_control!(visitor(DfsEvent::TreeEdge(node, child))).should_prune() {
+                continue;
+            }
+            discovered.visit(child);
+            time += 1;
+            if try_control!(visitor(DfsEvent::Discover(child, time))).should_prune() {
+                finished.visit(child);
+                time += 1;
+                try_control!(visitor(DfsEvent::Finish(child, time)));
+                continue;
+            }
+            stack.push((child, graph.children(child)));
+        }
+    }
+    C::continuing()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{depth_first_search, Control, DfsEvent};
+    use crate::graph::adjlist::Graph;
+
+    #[test]
+    fn test_events() {
+        let graph = Graph::from([(1, 2), (0, 1), (1, 0), (0, 2), (3, 2)]);
+        let mut events = Vec::new();
+        depth_first_search(&graph, 0..4, |event| events.push(event));
+        assert_eq!(
+            events,
+            [
+                DfsEvent::Discover(0, 1),
+                DfsEvent::TreeEdge(0, 2),
+                DfsEvent::Discover(2, 2),
+                DfsEvent::Finish(2, 3),
+                DfsEvent::TreeEdge(0, 1),
+                DfsEvent::Discover(1, 4),
+                DfsEvent::BackEdge(1, 0),
+                DfsEvent::CrossForwardEdge(1, 2),
+                DfsEvent::Finish(1, 5),
+                DfsEvent::Finish(0, 6),
+                DfsEvent::Discover(3, 7),
+                DfsEvent::CrossForwardEdge(3, 2),
+                DfsEvent::Finish(3, 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_control() {
+        let graph = Graph::from([(0, 1), (1, 2), (2, 3), (3, 1), (0, 4)]);
+        let back_edge = depth_first_search(&graph, [0], |event| match event {
+            DfsEvent::BackEdge(from, to) => Control::Break((from, to)),
+            _ => Control::Continue,
+        });
+        assert_eq!(back_edge.break_value(), Some((3, 1)));
+
+        // pruning the tree edge to 2 hides the cycle
+        let mut discovered = Vec::new();
+        let control = depth_first_search(&graph, [0], |event| match event {
+            DfsEvent::BackEdge(from, to) => Control::Break((from, to)),
+            DfsEvent::TreeEdge(_, 2) => Control::Prune,
+            DfsEvent::Discover(node, _) => {
+                discovered.push(node);
+                Control::Continue
+            }
+            _ => Control::Continue,
+        });
+        assert_eq!(control, Control::Continue);
+        assert_eq!(discovered, [0, 4, 1]);
+
+        let mut finished = Vec::new();
+        depth_first_search(&graph, [0], |event| match event {
+            DfsEvent::Discover(1, _) => Control::<()>::Prune,
+            DfsEvent::Finish(node, _) => {
+                finished.push(node);
+                Control::Continue
+            }
+            _ => Control::Continue,
+        });
+        assert_eq!(finished, [4, 1, 0]);
+    }
+}
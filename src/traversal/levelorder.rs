@@ -2,14 +2,16 @@
 
 use alloc::collections::VecDeque;
 
-use crate::graph::{traits::Children, visit_map::VisitMap};
+use crate::graph::{index::IndexType, traits::Children, visit_map::VisitMap};
 /// Level order traversal, aka breadth-first-search.
+///
+/// Nodes in the queue are stored as `Ix`, see [`IndexType`].
 #[derive(Clone, Debug)]
-pub struct LevelOrder<G> {
+pub struct LevelOrder<G, Ix = usize> {
     /// Reference to the graph
     graph: G,
     /// The queue of nodes to visit
-    queue: VecDeque<usize>,
+    queue: VecDeque<Ix>,
     /// The map of discovered nodes
     discovered: VisitMap,
 }
@@ -17,26 +19,163 @@ pub struct LevelOrder<G> {
 impl<G> LevelOrder<G> {
     /// Create a new `LevelOrder` iterator.
     pub fn new(graph: G, start: usize) -> Self {
+        Self::with_index_type(graph, start)
+    }
+
+    /// Create a new `LevelOrder` iterator that starts from all of the roots
+    /// at once, i.e. a multi-source breadth-first search.
+    pub fn from_roots<I>(graph: G, roots: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        Self::from_roots_with_index_type(graph, roots)
+    }
+}
+
+impl<G, Ix: IndexType> LevelOrder<G, Ix> {
+    /// Create a new `LevelOrder` iterator that stores nodes as `Ix`.
+    pub fn with_index_type(graph: G, start: usize) -> Self {
         let mut discovered = VisitMap::default();
         discovered.visit(start);
         Self {
             graph,
-            queue: VecDeque::from(vec![start]),
+            queue: VecDeque::from(vec![Ix::new(start)]),
+            discovered,
+        }
+    }
+
+    /// Create a new `LevelOrder` iterator from multiple roots, like
+    /// [`LevelOrder::from_roots`], that stores nodes as `Ix`.
+    pub fn from_roots_with_index_type<I>(graph: G, roots: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut discovered = VisitMap::default();
+        let queue = roots
+            .into_iter()
+            .filter(|&root| discovered.visit(root))
+            .map(Ix::new)
+            .collect();
+        Self {
+            graph,
+            queue,
             discovered,
         }
     }
+
+    /// Continues the traversal from `start`, keeping the discovered nodes.
+    /// Discards the nodes left to visit.
+    ///
+    /// Nothing more is visited if `start` was already discovered.
+    pub fn move_to(&mut self, start: usize) {
+        self.queue.clear();
+        if self.discovered.visit(start) {
+            self.queue.push_back(Ix::new(start));
+        }
+    }
 }
 
-impl<G: Children> Iterator for LevelOrder<G> {
+impl<G: Children, Ix: IndexType> Iterator for LevelOrder<G, Ix> {
     type Item = usize;
 
     fn next(&mut self) -> Option<usize> {
-        let node = self.queue.pop_front()?;
+        let node = self.queue.pop_front()?.index();
         for succ in self.graph.children(node) {
             if self.discovered.visit(succ) {
-                self.queue.push_back(succ);
+                self.queue.push_back(Ix::new(succ));
             }
         }
         Some(node)
     }
 }
+
+/// Level order traversal that also yields the depth of each node and the
+/// node it was discovered from, i.e. its parent in the breadth-first tree.
+///
+/// Yields `(node, depth, parent)`, where the start node has depth 0 and no
+/// parent. The depth is the number of edges on the shortest path to the node.
+///
+/// Nodes in the queue are stored as `Ix`, see [`IndexType`].
+#[derive(Clone, Debug)]
+pub struct Bfs<G, Ix = usize> {
+    /// Reference to the graph
+    graph: G,
+    /// The queue of nodes to visit, with their depth and parent
+    queue: VecDeque<(Ix, usize, Option<Ix>)>,
+    /// The map of discovered nodes
+    discovered: VisitMap,
+}
+
+impl<G> Bfs<G> {
+    /// Create a new `Bfs` iterator.
+    pub fn new(graph: G, start: usize) -> Self {
+        Self::with_index_type(graph, start)
+    }
+
+    /// Create a new `Bfs` iterator that starts from all of the roots at once.
+    /// Every root has depth 0, so the depth of a node is its distance to the
+    /// closest root.
+    pub fn from_roots<I>(graph: G, roots: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        Self::from_roots_with_index_type(graph, roots)
+    }
+}
+
+impl<G, Ix: IndexType> Bfs<G, Ix> {
+    /// Create a new `Bfs` iterator that stores nodes as `Ix`.
+    pub fn with_index_type(graph: G, start: usize) -> Self {
+        let mut discovered = VisitMap::default();
+        discovered.visit(start);
+        Self {
+            graph,
+            queue: VecDeque::from(vec![(Ix::new(start), 0, None)]),
+            discovered,
+        }
+    }
+
+    /// Create a new `Bfs` iterator from multiple roots, like
+    /// [`Bfs::from_roots`], that stores nodes as `Ix`.
+    pub fn from_roots_with_index_type<I>(graph: G, roots: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut discovered = VisitMap::default();
+        let queue = roots
+            .into_iter()
+            .filter(|&root| discovered.visit(root))
+            .map(|root| (Ix::new(root), 0, None))
+            .collect();
+        Self {
+            graph,
+            queue,
+            discovered,
+        }
+    }
+
+    /// Continues the traversal from `start` at depth 0, keeping the
+    /// discovered nodes. Discards the nodes left to visit.
+    ///
+    /// Nothing more is visited if `start` was already discovered.
+    pub fn move_to(&mut self, start: usize) {
+        self.queue.clear();
+        if self.discovered.visit(start) {
+            self.queue.push_back((Ix::new(start), 0, None));
+        }
+    }
+}
+
+impl<G: Children, Ix: IndexType> Iterator for Bfs<G, Ix> {
+    type Item = (usize, usize, Option<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, depth, parent) = self.queue.pop_front()?;
+        for succ in self.graph.children(node.index()) {
+            if self.discovered.visit(succ) {
+                self.queue.push_back((Ix::new(succ), depth + 1, Some(node)));
+            }
+        }
+        Some((node.index(), depth, parent.map(IndexType::index)))
+    }
+}
@@ -1,4 +1,5 @@
 //! Traversal algorithms.
+pub mod dfs;
 pub mod levelorder;
 pub mod postorder;
 pub mod preorder;
@@ -7,8 +8,11 @@ pub mod topological;
 #[cfg(test)]
 mod tests {
     use crate::{
-        graph::{matrix::Graph, traits::Directed},
-        traversal::{levelorder::LevelOrder, postorder::PostOrder},
+        graph::{index::NonMaxU32, matrix::Graph, traits::Directed},
+        traversal::{
+            levelorder::{Bfs, LevelOrder},
+            postorder::PostOrder,
+        },
     };
 
     use super::preorder::PreOrder;
@@ -31,6 +35,20 @@ mod tests {
         assert_eq!(order, [1, 3, 6, 5, 8, 7, 2, 4]);
         let order = LevelOrder::new(&graph, 1).collect::<Vec<_>>();
         assert_eq!(order, [1, 2, 3, 4, 5, 6, 7, 8]);
+        let tree = Bfs::new(&graph, 1).collect::<Vec<_>>();
+        assert_eq!(
+            tree,
+            [
+                (1, 0, None),
+                (2, 1, Some(1)),
+                (3, 1, Some(1)),
+                (4, 2, Some(2)),
+                (5, 2, Some(3)),
+                (6, 2, Some(3)),
+                (7, 3, Some(5)),
+                (8, 3, Some(5))
+            ]
+        );
         let order = PostOrder::new(&graph, 1).collect::<Vec<_>>();
         assert_eq!(order, [6, 8, 7, 5, 3, 4, 2, 1]);
 
@@ -76,4 +94,65 @@ mod tests {
         let order = PostOrder::new(&graph, 2).collect::<Vec<_>>();
         assert_eq!(order, vec![1, 4, 3, 2]);
     }
+
+    #[test]
+    fn test_forest() {
+        // two components: 0 -> 1 -> 2 and 3 -> 4, with 5 on its own
+        let graph = Graph::<_, Directed>::from([(0, 1), (1, 2), (3, 4), (5, 5)]);
+
+        let order = PreOrder::from_roots(&graph, [1, 0, 4, 3, 5]).collect::<Vec<_>>();
+        assert_eq!(order, [1, 2, 0, 4, 3, 5]);
+        let order = PostOrder::from_roots(&graph, 0..6).collect::<Vec<_>>();
+        assert_eq!(order, [2, 1, 0, 4, 3, 5]);
+        let order = PreOrder::<_, u16>::from_roots_with_index_type(&graph, [1, 0, 4, 3, 5]);
+        assert_eq!(order.collect::<Vec<_>>(), [1, 2, 0, 4, 3, 5]);
+        let order = PostOrder::<_, NonMaxU32>::from_roots_with_index_type(&graph, 0..6);
+        assert_eq!(order.collect::<Vec<_>>(), [2, 1, 0, 4, 3, 5]);
+
+        let mut preorder = PreOrder::new(&graph, 1);
+        let mut order = preorder.by_ref().collect::<Vec<_>>();
+        for root in 0..6 {
+            preorder.move_to(root);
+            order.extend(&mut preorder);
+        }
+        assert_eq!(order, [1, 2, 0, 3, 4, 5]);
+
+        let mut postorder = PostOrder::new(&graph, 3);
+        let mut order = postorder.by_ref().collect::<Vec<_>>();
+        postorder.move_to(4);
+        order.extend(&mut postorder);
+        postorder.move_to(1);
+        order.extend(&mut postorder);
+        assert_eq!(order, [4, 3, 2, 1]);
+
+        let mut levelorder = LevelOrder::new(&graph, 3);
+        assert_eq!(levelorder.by_ref().count(), 2);
+        levelorder.move_to(4);
+        assert_eq!(levelorder.next(), None);
+        levelorder.move_to(0);
+        assert_eq!(levelorder.collect::<Vec<_>>(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_multi_source() {
+        let graph = Graph::<_, Directed>::from([(0, 1), (1, 2), (2, 3), (3, 4), (5, 4), (4, 6)]);
+        let order = LevelOrder::from_roots(&graph, [0, 5, 0]).collect::<Vec<_>>();
+        assert_eq!(order, [0, 5, 1, 4, 2, 6, 3]);
+        let order = LevelOrder::<_, u16>::from_roots_with_index_type(&graph, [0, 5, 0]);
+        assert_eq!(order.collect::<Vec<_>>(), [0, 5, 1, 4, 2, 6, 3]);
+        let depths = Bfs::<_, u16>::from_roots_with_index_type(&graph, [0, 5]);
+        assert_eq!(depths.map(|(_, depth, _)| depth).max(), Some(3));
+        let depths = Bfs::from_roots(&graph, [0, 5])
+            .map(|(node, depth, _)| (node, depth))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            depths,
+            [(0, 0), (5, 0), (1, 1), (4, 1), (2, 2), (6, 2), (3, 3)]
+        );
+
+        let mut bfs = Bfs::new(&graph, 5);
+        assert_eq!(bfs.by_ref().count(), 3);
+        bfs.move_to(2);
+        assert_eq!(bfs.collect::<Vec<_>>(), [(2, 0, None), (3, 1, Some(2))]);
+    }
 }
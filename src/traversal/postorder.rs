@@ -1,13 +1,15 @@
 //! Postorder traversal
 
-use crate::graph::{traits::Children, visit_map::VisitMap};
+use crate::graph::{index::IndexType, traits::Children, visit_map::VisitMap};
 /// Post order traversal.
+///
+/// Nodes on the stack are stored as `Ix`, see [`IndexType`].
 #[derive(Clone, Debug)]
-pub struct PostOrder<G> {
+pub struct PostOrder<G, Ix = usize> {
     /// Reference to the graph
     graph: G,
     /// The stack of nodes to visit
-    stack: Vec<usize>,
+    stack: Vec<Ix>,
     /// The map of discovered nodes
     discovered: VisitMap,
     /// The map of finished nodes
@@ -17,24 +19,70 @@ pub struct PostOrder<G> {
 impl<G> PostOrder<G> {
     /// Create a new `PostOrder` iterator.
     pub fn new(graph: G, start: usize) -> Self {
+        Self::with_index_type(graph, start)
+    }
+
+    /// Create a new `PostOrder` iterator that visits the nodes reachable from
+    /// each of the roots in turn, skipping roots that were already visited.
+    /// Visiting every node as a root gives a depth-first forest.
+    pub fn from_roots<I>(graph: G, roots: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        Self::from_roots_with_index_type(graph, roots)
+    }
+}
+
+impl<G, Ix: IndexType> PostOrder<G, Ix> {
+    /// Create a new `PostOrder` iterator that stores nodes as `Ix`.
+    pub fn with_index_type(graph: G, start: usize) -> Self {
         Self {
             graph,
-            stack: vec![start],
+            stack: vec![Ix::new(start)],
             discovered: VisitMap::default(),
             finished: VisitMap::default(),
         }
     }
+
+    /// Create a new `PostOrder` iterator from multiple roots, like
+    /// [`PostOrder::from_roots`], that stores nodes as `Ix`.
+    pub fn from_roots_with_index_type<I>(graph: G, roots: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut stack = roots.into_iter().map(Ix::new).collect::<Vec<_>>();
+        stack.reverse();
+        Self {
+            graph,
+            stack,
+            discovered: VisitMap::default(),
+            finished: VisitMap::default(),
+        }
+    }
+
+    /// Continues the traversal from `start`, keeping the discovered and
+    /// finished nodes. Discards the nodes left to visit, so nodes that were
+    /// discovered but not finished are never yielded.
+    ///
+    /// Nothing more is visited if `start` was already discovered.
+    pub fn move_to(&mut self, start: usize) {
+        self.stack.clear();
+        if !self.discovered.is_visited(start) {
+            self.stack.push(Ix::new(start));
+        }
+    }
 }
 
-impl<G: Children> Iterator for PostOrder<G> {
+impl<G: Children, Ix: IndexType> Iterator for PostOrder<G, Ix> {
     type Item = usize;
 
     fn next(&mut self) -> Option<usize> {
         while let Some(&node) = self.stack.last() {
+            let node = node.index();
             if self.discovered.visit(node) {
                 for succ in self.graph.children(node) {
                     if !self.discovered.is_visited(succ) {
-                        self.stack.push(succ);
+                        self.stack.push(Ix::new(succ));
                     }
                 }
             } else {
@@ -1,14 +1,18 @@
 //! Preorder traversal
 
-use crate::graph::{traits::Children, visit_map::VisitMap};
+use crate::graph::{index::IndexType, traits::Children, visit_map::VisitMap};
 
 /// Preorder traversal.
+///
+/// Nodes on the stack are stored as `Ix`, see [`IndexType`].
 #[derive(Clone, Debug)]
-pub struct PreOrder<G> {
+pub struct PreOrder<G, Ix = usize> {
     /// Reference to the graph
     graph: G,
     /// The stack of nodes to visit
-    stack: Vec<usize>,
+    stack: Vec<Ix>,
+    /// The roots to continue from once the stack is empty, in reverse order
+    roots: Vec<Ix>,
     /// The map of discovered nodes
     discovered: VisitMap,
 }
@@ -16,24 +20,78 @@ pub struct PreOrder<G> {
 impl<G> PreOrder<G> {
     /// Create a new `PreOrder` iterator.
     pub fn new(graph: G, start: usize) -> Self {
+        Self::with_index_type(graph, start)
+    }
+
+    /// Create a new `PreOrder` iterator that visits the nodes reachable from
+    /// each of the roots in turn, skipping roots that were already visited.
+    /// Visiting every node as a root gives a depth-first forest.
+    pub fn from_roots<I>(graph: G, roots: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        Self::from_roots_with_index_type(graph, roots)
+    }
+}
+
+impl<G, Ix: IndexType> PreOrder<G, Ix> {
+    /// Create a new `PreOrder` iterator that stores nodes as `Ix`.
+    pub fn with_index_type(graph: G, start: usize) -> Self {
         let mut discovered = VisitMap::default();
         discovered.visit(start);
         Self {
             graph,
-            stack: vec![start],
+            stack: vec![Ix::new(start)],
+            roots: Vec::new(),
             discovered,
         }
     }
+
+    /// Create a new `PreOrder` iterator from multiple roots, like
+    /// [`PreOrder::from_roots`], that stores nodes as `Ix`.
+    pub fn from_roots_with_index_type<I>(graph: G, roots: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut roots = roots.into_iter().map(Ix::new).collect::<Vec<_>>();
+        roots.reverse();
+        Self {
+            graph,
+            stack: Vec::new(),
+            roots,
+            discovered: VisitMap::default(),
+        }
+    }
+
+    /// Continues the traversal from `start`, keeping the discovered nodes.
+    /// Discards the nodes and roots left to visit.
+    ///
+    /// Nothing more is visited if `start` was already discovered.
+    pub fn move_to(&mut self, start: usize) {
+        self.stack.clear();
+        self.roots.clear();
+        if self.discovered.visit(start) {
+            self.stack.push(Ix::new(start));
+        }
+    }
 }
 
-impl<G: Children> Iterator for PreOrder<G> {
+impl<G: Children, Ix: IndexType> Iterator for PreOrder<G, Ix> {
     type Item = usize;
 
     fn next(&mut self) -> Option<usize> {
-        let node = self.stack.pop()?;
+        let node = match self.stack.pop() {
+            Some(node) => node.index(),
+            None => loop {
+                let root = self.roots.pop()?.index();
+                if self.discovered.visit(root) {
+                    break root;
+                }
+            },
+        };
         for succ in self.graph.children(node) {
             if self.discovered.visit(succ) {
-                self.stack.push(succ);
+                self.stack.push(Ix::new(succ));
             }
         }
         Some(node)
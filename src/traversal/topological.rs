@@ -1,20 +1,32 @@
 //! Topological traversal
 //! <https://en.wikipedia.org/wiki/Topological_sorting>
 
-use crate::graph::traits::{Children, NodeCount};
+use alloc::collections::BinaryHeap;
+use core::{cmp::Reverse, fmt};
+
+use super::dfs::{depth_first_search, Control, DfsEvent};
+use crate::{
+    graph::{
+        index::IndexType,
+        traits::{Children, NodeCount},
+    },
+    Error,
+};
 
 /// Topological traversal.
 /// Works for directed, acyclic graphs. Uses Kahn's algorithm.
 /// Time complexity: O(|V| + |E|)
 /// Space complexity: O(|V|)
+///
+/// Nodes on the stack are stored as `Ix`, see [`IndexType`].
 #[derive(Clone, Debug)]
-pub struct Topological<G> {
+pub struct Topological<G, Ix = usize> {
     /// Reference to the graph
     graph: G,
     /// The in-degree of each node
     in_degree: Vec<usize>,
     /// The stack of nodes with no parents
-    stack: Vec<usize>,
+    stack: Vec<Ix>,
 }
 
 impl<G> Topological<G>
@@ -23,17 +35,27 @@ where
 {
     /// Create a new `Topological` iterator.
     pub fn new(graph: G) -> Self {
-        let mut in_degree = vec![0; graph.node_count()];
-        for node in 0..graph.node_count() {
-            for child in graph.children(node) {
-                in_degree[child] += 1;
-            }
-        }
+        Self::with_index_type(graph)
+    }
+}
+
+impl<G, Ix> Topological<G, Ix>
+where
+    G: NodeCount + Children,
+    Ix: IndexType,
+{
+    /// Create a new `Topological` iterator that stores nodes as `Ix`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node index doesn't fit in the index type.
+    pub fn with_index_type(graph: G) -> Self {
+        let in_degree = in_degrees(graph);
         let stack = in_degree
             .iter()
             .enumerate()
-            .filter(|&(_, &degree)| degree == 0)
-            .map(|(i, _)| i)
+            .filter(|&(node, &degree)| degree == 0 && graph.contains_node(node))
+            .map(|(i, _)| Ix::new(i))
             .collect();
 
         Self {
@@ -44,26 +66,332 @@ where
     }
 }
 
-impl<G: Children> Iterator for Topological<G> {
+impl<G, Ix> Topological<G, Ix> {
+    /// Returns the nodes that haven't been yielded and can't be yet, because
+    /// some of their parents haven't been yielded.
+    ///
+    /// Once the iterator is exhausted, these are the nodes on a cycle or
+    /// reachable from one. The graph is acyclic if there are none.
+    pub fn remaining(&self) -> impl Iterator<Item = usize> + '_ {
+        self.in_degree
+            .iter()
+            .enumerate()
+            .filter(|&(_, &degree)| degree > 0)
+            .map(|(node, _)| node)
+    }
+}
+
+impl<G: Children, Ix: IndexType> Iterator for Topological<G, Ix> {
     type Item = usize;
 
     fn next(&mut self) -> Option<usize> {
-        let node = self.stack.pop()?;
+        let node = self.stack.pop()?.index();
         for child in self.graph.children(node) {
             self.in_degree[child] -= 1;
             if self.in_degree[child] == 0 {
-                self.stack.push(child);
+                self.stack.push(Ix::new(child));
             }
         }
         Some(node)
     }
 }
 
+/// Topological traversal that yields the node with the smallest key among
+/// the nodes whose parents have all been yielded, breaking ties by the
+/// smallest index.
+///
+/// Unlike [`Topological`], the order doesn't depend on the order that
+/// children are stored in, so it only changes when the keys or the
+/// dependencies of the nodes change.
+///
+/// Time complexity: O(|V| log |V| + |E|)
+/// Space complexity: O(|V|)
+#[derive(Clone, Debug)]
+pub struct PriorityTopological<G, K, F> {
+    /// Reference to the graph
+    graph: G,
+    /// The in-degree of each node
+    in_degree: Vec<usize>,
+    /// The nodes with no parents, smallest key first
+    heap: BinaryHeap<Reverse<(K, usize)>>,
+    /// Maps a node to its key
+    key: F,
+}
+
+impl<G, K, F> PriorityTopological<G, K, F>
+where
+    G: NodeCount + Children,
+    K: Ord,
+    F: FnMut(usize) -> K,
+{
+    /// Create a new `PriorityTopological` iterator ordered by `key`.
+    pub fn new(graph: G, mut key: F) -> Self {
+        let in_degree = in_degrees(graph);
+        let heap = in_degree
+            .iter()
+            .enumerate()
+            .filter(|&(node, &degree)| degree == 0 && graph.contains_node(node))
+            .map(|(node, _)| Reverse((key(node), node)))
+            .collect();
+        Self {
+            graph,
+            in_degree,
+            heap,
+            key,
+        }
+    }
+}
+
+impl<G> PriorityTopological<G, usize, fn(usize) -> usize>
+where
+    G: NodeCount + Children,
+{
+    /// Create a new `PriorityTopological` iterator that yields the
+    /// lexicographically smallest topological order.
+    pub fn lexicographic(graph: G) -> Self {
+        Self::new(graph, |node| node)
+    }
+}
+
+impl<G, K, F> PriorityTopological<G, K, F> {
+    /// Returns the nodes that haven't been yielded and can't be yet, like
+    /// [`Topological::remaining`].
+    pub fn remaining(&self) -> impl Iterator<Item = usize> + '_ {
+        self.in_degree
+            .iter()
+            .enumerate()
+            .filter(|&(_, &degree)| degree > 0)
+            .map(|(node, _)| node)
+    }
+}
+
+impl<G, K, F> Iterator for PriorityTopological<G, K, F>
+where
+    G: Children,
+    K: Ord,
+    F: FnMut(usize) -> K,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let Reverse((_, node)) = self.heap.pop()?;
+        for child in self.graph.children(node) {
+            self.in_degree[child] -= 1;
+            if self.in_degree[child] == 0 {
+                self.heap.push(Reverse(((self.key)(child), child)));
+            }
+        }
+        Some(node)
+    }
+}
+
+/// Iterator over every topological order of a graph, in lexicographic order.
+///
+/// Backtracks over the same in-degree bookkeeping as [`Topological`],
+/// undoing the last choice once every order starting with it was yielded.
+/// Yields nothing if the graph has a cycle, which is checked up front.
+///
+/// Time complexity: O(|V| + |E|) to check for a cycle, then O(|V|^2 + |E|)
+/// per order
+/// Space complexity: O(|V|)
+#[derive(Clone, Debug)]
+pub struct AllTopological<G> {
+    /// Reference to the graph
+    graph: G,
+    /// The in-degree of each node, ignoring the nodes in `order`
+    in_degree: Vec<usize>,
+    /// Whether each node is in `order`, vacant slots are always placed
+    placed: Vec<bool>,
+    /// The number of nodes in a complete order
+    node_count: usize,
+    /// The order built so far
+    order: Vec<usize>,
+    /// The smallest node to try next at the end of `order`
+    cursor: usize,
+    /// Whether every order was yielded
+    done: bool,
+}
+
+impl<G> AllTopological<G>
+where
+    G: NodeCount + Children,
+{
+    /// Create a new `AllTopological` iterator.
+    pub fn new(graph: G) -> Self {
+        let in_degree = in_degrees(graph);
+        let placed = (0..graph.node_count())
+            .map(|node| !graph.contains_node(node))
+            .collect::<Vec<_>>();
+        let node_count = placed.iter().filter(|&&placed| !placed).count();
+        // Backtracking would try every partial order before giving up on a
+        // cycle, so check that every node can be ordered first.
+        let acyclic = Topological::new(graph).count() == node_count;
+        Self {
+            graph,
+            placed,
+            node_count,
+            in_degree,
+            order: Vec::new(),
+            cursor: 0,
+            done: !acyclic,
+        }
+    }
+
+    /// Appends a node with no remaining parents to the order.
+    fn place(&mut self, node: usize) {
+        self.placed[node] = true;
+        for child in self.graph.children(node) {
+            self.in_degree[child] -= 1;
+        }
+        self.order.push(node);
+        self.cursor = 0;
+    }
+
+    /// Removes the last node from the order so the next one can be tried.
+    /// Returns `false` if the order is empty.
+    fn backtrack(&mut self) -> bool {
+        let Some(node) = self.order.pop() else {
+            return false;
+        };
+        self.placed[node] = false;
+        for child in self.graph.children(node) {
+            self.in_degree[child] += 1;
+        }
+        self.cursor = node + 1;
+        true
+    }
+}
+
+impl<G> Iterator for AllTopological<G>
+where
+    G: NodeCount + Children,
+{
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.done {
+            return None;
+        }
+        if self.node_count == 0 {
+            self.done = true;
+            return Some(Vec::new());
+        }
+        loop {
+            let candidate = (self.cursor..self.in_degree.len())
+                .find(|&node| !self.placed[node] && self.in_degree[node] == 0);
+            if let Some(node) = candidate {
+                self.place(node);
+                if self.order.len() == self.node_count {
+                    let order = self.order.clone();
+                    self.backtrack();
+                    return Some(order);
+                }
+            } else if !self.backtrack() {
+                self.done = true;
+                return None;
+            }
+        }
+    }
+}
+
+/// Returns the number of parents of each node.
+fn in_degrees<G>(graph: G) -> Vec<usize>
+where
+    G: NodeCount + Children,
+{
+    let mut in_degree = vec![0; graph.node_count()];
+    for node in 0..graph.node_count() {
+        for child in graph.children(node) {
+            in_degree[child] += 1;
+        }
+    }
+    in_degree
+}
+
+/// A cycle that prevents a graph from being sorted topologically.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cycle(Vec<usize>);
+
+impl Cycle {
+    /// Returns the nodes of the cycle in edge order. The last node has an edge
+    /// back to the first one.
+    ///
+    /// If [`toposort`] couldn't find the cycle, e.g. because the graph's
+    /// children changed while it was searching, these are the nodes that
+    /// couldn't be ordered instead.
+    #[must_use]
+    pub fn nodes(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle through nodes {:?}", self.0)
+    }
+}
+
+impl std::error::Error for Cycle {}
+
+impl From<Cycle> for Error {
+    fn from(cycle: Cycle) -> Self {
+        Self::CycleDetected(cycle.0)
+    }
+}
+
+/// Returns the nodes of a directed graph in topological order.
+///
+/// Time complexity: **O(|V| + |E|)**
+///
+/// # Errors
+///
+/// Returns one of the cycles if the graph isn't acyclic. If the children of
+/// a node change between calls to [`Children::children`] and no cycle can be
+/// found, returns the nodes that couldn't be ordered instead.
+pub fn toposort<G>(graph: G) -> Result<Vec<usize>, Cycle>
+where
+    G: NodeCount + Children,
+{
+    let mut topological = Topological::new(graph);
+    let order = topological.by_ref().collect::<Vec<_>>();
+    if topological.remaining().next().is_none() {
+        return Ok(order);
+    }
+
+    // every remaining node is on a cycle or behind one, so a search from
+    // them is bound to run into a back edge
+    let mut parents = vec![None; graph.node_count()];
+    let back_edge = depth_first_search(graph, topological.remaining(), |event| match event {
+        DfsEvent::TreeEdge(parent, child) => {
+            parents[child] = Some(parent);
+            Control::Continue
+        }
+        DfsEvent::BackEdge(from, to) => Control::Break((from, to)),
+        _ => Control::Continue,
+    });
+    let Some((from, to)) = back_edge.break_value() else {
+        return Err(Cycle(topological.remaining().collect()));
+    };
+    // the ancestors of `from` lead back to `to`
+    let mut cycle = vec![from];
+    let mut node = from;
+    while node != to {
+        let Some(parent) = parents[node] else {
+            break;
+        };
+        cycle.push(parent);
+        node = parent;
+    }
+    cycle.reverse();
+    Err(Cycle(cycle))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        graph::{matrix::Graph, traits::Directed},
-        traversal::topological::Topological,
+        graph::{index::NonMaxU32, matrix::Graph, traits::Directed},
+        traversal::topological::{toposort, AllTopological, PriorityTopological, Topological},
+        Error,
     };
 
     #[test]
@@ -72,5 +400,68 @@ mod tests {
             Graph::<_, Directed>::from([(0, 1), (1, 2), (0, 3), (3, 1), (3, 5), (3, 4), (4, 5)]);
         let order = Topological::new(&graph).collect::<Vec<_>>();
         assert_eq!(order, [0, 3, 4, 5, 1, 2]);
+        let order = Topological::<_, NonMaxU32>::with_index_type(&graph).collect::<Vec<_>>();
+        assert_eq!(order, [0, 3, 4, 5, 1, 2]);
+    }
+
+    #[test]
+    fn test_toposort() {
+        let graph = Graph::<_, Directed>::from([(0, 1), (1, 2), (0, 3), (3, 2)]);
+        assert_eq!(toposort(&graph), Ok(vec![0, 3, 1, 2]));
+
+        let graph = Graph::<_, Directed>::from([(0, 1), (1, 2), (2, 3), (3, 1), (3, 4), (5, 5)]);
+        let mut topological = Topological::new(&graph);
+        assert_eq!(topological.by_ref().collect::<Vec<_>>(), [0]);
+        assert_eq!(topological.remaining().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+
+        let cycle = toposort(&graph).map_err(|cycle| cycle.nodes().to_vec());
+        assert_eq!(cycle, Err(vec![1, 2, 3]));
+        let error = toposort(&graph).map_err(Error::from);
+        assert_eq!(error, Err(Error::CycleDetected(vec![1, 2, 3])));
+
+        let graph = Graph::<_, Directed>::from([(0, 0)]);
+        let cycle = toposort(&graph).map_err(|cycle| cycle.nodes().to_vec());
+        assert_eq!(cycle, Err(vec![0]));
+    }
+
+    #[test]
+    fn test_priority() {
+        let edges = [(0, 1), (1, 2), (0, 3), (3, 1), (3, 5), (3, 4), (4, 5)];
+        let graph = Graph::<_, Directed>::from(edges);
+        let order = PriorityTopological::lexicographic(&graph).collect::<Vec<_>>();
+        assert_eq!(order, [0, 3, 1, 2, 4, 5]);
+
+        // largest first
+        let order = PriorityTopological::new(&graph, core::cmp::Reverse);
+        assert_eq!(order.collect::<Vec<_>>(), [0, 3, 4, 5, 1, 2]);
+
+        // a node becomes available when its last parent is yielded
+        let graph = Graph::<_, Directed>::from([(6, 7), (2, 3), (0, 7)]);
+        let order = PriorityTopological::new(&graph, |node| node % 3);
+        assert_eq!(order.collect::<Vec<_>>(), [0, 6, 1, 4, 7, 2, 3, 5]);
+
+        let graph = Graph::<_, Directed>::from([(0, 1), (1, 0), (0, 2)]);
+        let mut order = PriorityTopological::lexicographic(&graph);
+        assert_eq!(order.next(), None);
+        assert_eq!(order.remaining().collect::<Vec<_>>(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_all() {
+        let graph = Graph::<_, Directed>::from([(0, 1), (0, 2), (1, 3), (2, 3)]);
+        let orders = AllTopological::new(&graph).collect::<Vec<_>>();
+        assert_eq!(orders, [[0, 1, 2, 3], [0, 2, 1, 3]]);
+
+        let graph = Graph::<(), Directed>::with_capacity(3);
+        assert_eq!(AllTopological::new(&graph).count(), 6);
+
+        let graph = Graph::<_, Directed>::from([(0, 1), (1, 2), (2, 1), (0, 3)]);
+        assert_eq!(AllTopological::new(&graph).next(), None);
+
+        // the cycle is found without trying the orders of the free nodes
+        let mut graph = Graph::<(), Directed>::with_capacity(22);
+        graph.add_edge(0, 1, ());
+        graph.add_edge(1, 0, ());
+        assert_eq!(AllTopological::new(&graph).next(), None);
     }
 }